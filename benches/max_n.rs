@@ -0,0 +1,43 @@
+#![feature(test)]
+
+extern crate test;
+
+use advent_2022::IteratorUtils;
+use test::{black_box, Bencher};
+
+/// Deterministic pseudo-random values, so runs are comparable.
+fn values(len: usize) -> Vec<u64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect()
+}
+
+#[bench]
+fn max_n_3(b: &mut Bencher) {
+    let values = values(10_000);
+    b.iter(|| black_box(&values).iter().max_n::<3>());
+}
+
+#[bench]
+fn max_k_3(b: &mut Bencher) {
+    let values = values(10_000);
+    b.iter(|| black_box(&values).iter().max_k(3));
+}
+
+#[bench]
+fn max_n_256(b: &mut Bencher) {
+    let values = values(10_000);
+    b.iter(|| black_box(&values).iter().max_n::<256>());
+}
+
+#[bench]
+fn max_k_256(b: &mut Bencher) {
+    let values = values(10_000);
+    b.iter(|| black_box(&values).iter().max_k(256));
+}
@@ -0,0 +1,481 @@
+use std::fmt::{self, Debug};
+
+/// A fixed-capacity set of small integers, stored as `WORDS` 64-bit words.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize>([u64; WORDS]);
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * 64;
+
+    pub const fn new() -> Self {
+        Self([0; WORDS])
+    }
+
+    pub const fn from_words(words: [u64; WORDS]) -> Self {
+        Self(words)
+    }
+
+    pub const fn words(&self) -> &[u64; WORDS] {
+        &self.0
+    }
+
+    pub const fn len(&self) -> usize {
+        let mut len = 0;
+        let mut i = 0;
+        while i < WORDS {
+            len += self.0[i].count_ones() as usize;
+            i += 1;
+        }
+        len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        let mut i = 0;
+        while i < WORDS {
+            if self.0[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    pub const fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.0[value / 64] & (1 << (value % 64)) != 0
+    }
+
+    /// Returns whether the value was newly added.
+    pub const fn insert(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        self.0[value / 64] |= 1 << (value % 64);
+        !was_present
+    }
+
+    /// Returns whether the value was present.
+    pub const fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if was_present {
+            self.0[value / 64] &= !(1 << (value % 64));
+        }
+        was_present
+    }
+
+    pub const fn union(&self, other: &Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] |= other.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+
+    pub const fn intersection(&self, other: &Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] &= other.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+
+    pub const fn difference(&self, other: &Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] &= !other.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+
+    pub const fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] ^= other.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub const fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub const fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    pub const fn first(&self) -> Option<usize> {
+        let mut i = 0;
+        while i < WORDS {
+            if self.0[i] != 0 {
+                return Some(i * 64 + self.0[i].trailing_zeros() as usize);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    pub const fn pop_first(&mut self) -> Option<usize> {
+        let first = self.first();
+        if let Some(value) = first {
+            self.0[value / 64] &= !(1 << (value % 64));
+        }
+        first
+    }
+
+    /// The number of values in the set that are less than `value`.
+    pub const fn rank(&self, value: usize) -> usize {
+        let value = if value < Self::CAPACITY {
+            value
+        } else {
+            Self::CAPACITY
+        };
+        let mut rank = 0;
+        let mut i = 0;
+        while i < value / 64 {
+            rank += self.0[i].count_ones() as usize;
+            i += 1;
+        }
+        if value % 64 != 0 {
+            rank += (self.0[value / 64] & ((1 << (value % 64)) - 1)).count_ones() as usize;
+        }
+        rank
+    }
+
+    /// The `n`th smallest value in the set, counting from 0.
+    pub const fn select(&self, mut n: usize) -> Option<usize> {
+        let mut i = 0;
+        while i < WORDS {
+            let ones = self.0[i].count_ones() as usize;
+            if n < ones {
+                let mut word = self.0[i];
+                while n > 0 {
+                    word &= word - 1;
+                    n -= 1;
+                }
+                return Some(i * 64 + word.trailing_zeros() as usize);
+            }
+            n -= ones;
+            i += 1;
+        }
+        None
+    }
+
+    pub const fn iter(&self) -> BitSetIter<WORDS> {
+        BitSetIter(*self)
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<const WORDS: usize> Extend<usize> for BitSet<WORDS> {
+    fn extend<T: IntoIterator<Item = usize>>(&mut self, iter: T) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<const WORDS: usize> IntoIterator for BitSet<WORDS> {
+    type Item = usize;
+
+    type IntoIter = BitSetIter<WORDS>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const WORDS: usize> IntoIterator for &BitSet<WORDS> {
+    type Item = usize;
+
+    type IntoIter = BitSetIter<WORDS>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const WORDS: usize> Debug for BitSet<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct BitSetIter<const WORDS: usize>(BitSet<WORDS>);
+
+impl<const WORDS: usize> BitSetIter<WORDS> {
+    pub const fn into_remaining(self) -> BitSet<WORDS> {
+        self.0
+    }
+}
+
+impl<const WORDS: usize> Iterator for BitSetIter<WORDS> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.0.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len();
+        (len, Some(len))
+    }
+}
+
+impl<const WORDS: usize> ExactSizeIterator for BitSetIter<WORDS> {}
+
+/// A set of small integers that grows to fit the largest value inserted.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct GrowableBitSet {
+    // Never has trailing zero words, so that equal sets compare equal
+    words: Vec<u64>,
+}
+
+impl GrowableBitSet {
+    pub const fn new() -> Self {
+        Self { words: vec![] }
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn from_words(mut words: Vec<u64>) -> Self {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        Self { words }
+    }
+
+    fn zip_words(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.words.len().max(other.words.len());
+        let word = |words: &[u64], i| words.get(i).copied().unwrap_or(0);
+        Self::from_words(
+            (0..len)
+                .map(|i| f(word(&self.words, i), word(&other.words, i)))
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let word = self.words.get(value / 64).copied().unwrap_or(0);
+        word & (1 << (value % 64)) != 0
+    }
+
+    /// Returns whether the value was newly added.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if self.words.len() <= value / 64 {
+            self.words.resize(value / 64 + 1, 0);
+        }
+        self.words[value / 64] |= 1 << (value % 64);
+        !was_present
+    }
+
+    /// Returns whether the value was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if was_present {
+            self.words[value / 64] &= !(1 << (value % 64));
+            *self = Self::from_words(std::mem::take(&mut self.words));
+        }
+        was_present
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a ^ b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        let (i, word) = self.words.iter().enumerate().find(|(_, &w)| w != 0)?;
+        Some(i * 64 + word.trailing_zeros() as usize)
+    }
+
+    /// The number of values in the set that are less than `value`.
+    pub fn rank(&self, value: usize) -> usize {
+        let full_words = (value / 64).min(self.words.len());
+        let mut rank: usize = self.words[..full_words]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum();
+        if let Some(word) = self.words.get(value / 64) {
+            rank += (word & ((1 << (value % 64)) - 1)).count_ones() as usize;
+        }
+        rank
+    }
+
+    /// The `n`th smallest value in the set, counting from 0.
+    pub fn select(&self, n: usize) -> Option<usize> {
+        self.iter().nth(n)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    None
+                } else {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(i * 64 + bit)
+                }
+            })
+        })
+    }
+}
+
+impl FromIterator<usize> for GrowableBitSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for GrowableBitSet {
+    fn extend<T: IntoIterator<Item = usize>>(&mut self, iter: T) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl Debug for GrowableBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENS: BitSet<2> = BitSet::from_words([0x5555_5555_5555_5555; 2]);
+
+    #[test]
+    fn bit_set_basic() {
+        let mut set = BitSet::<2>::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(100));
+        assert!(!set.insert(3));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(100));
+        assert!(!set.contains(99));
+        assert!(!set.contains(1000));
+        assert_eq!(format!("{set:?}"), "{3, 100}");
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.pop_first(), Some(100));
+        assert_eq!(set.pop_first(), None);
+        assert_eq!(EVENS.len(), 64);
+    }
+
+    #[test]
+    fn bit_set_operations() {
+        let a: BitSet<2> = [1, 2, 3, 70].into_iter().collect();
+        let b: BitSet<2> = [3, 4, 70, 127].into_iter().collect();
+        assert_eq!(
+            a.union(&b).iter().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 70, 127]
+        );
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [3, 70]);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(
+            a.symmetric_difference(&b).iter().collect::<Vec<_>>(),
+            [1, 2, 4, 127],
+        );
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(a.is_superset(&a.difference(&b)));
+        assert!(!a.is_subset(&b));
+        assert!(a.difference(&b).is_disjoint(&b));
+    }
+
+    #[test]
+    fn bit_set_rank_select() {
+        let set: BitSet<2> = [1, 2, 3, 70, 127].into_iter().collect();
+        assert_eq!(set.rank(0), 0);
+        assert_eq!(set.rank(3), 2);
+        assert_eq!(set.rank(64), 3);
+        assert_eq!(set.rank(71), 4);
+        assert_eq!(set.rank(1000), 5);
+        for (n, value) in set.iter().enumerate() {
+            assert_eq!(set.select(n), Some(value));
+            assert_eq!(set.rank(value), n);
+        }
+        assert_eq!(set.select(5), None);
+        assert_eq!(EVENS.select(40), Some(80));
+    }
+
+    #[test]
+    fn growable_bit_set() {
+        let mut a: GrowableBitSet = [1, 500, 64].into_iter().collect();
+        let b: GrowableBitSet = [64, 2].into_iter().collect();
+        assert_eq!(format!("{a:?}"), "{1, 64, 500}");
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [64]);
+        assert_eq!(a.symmetric_difference(&b).len(), 3);
+        assert_eq!(a.rank(500), 2);
+        assert_eq!(a.rank(10_000), 3);
+        assert_eq!(a.select(2), Some(500));
+        assert!(a.remove(500));
+        assert_eq!(a.words().len(), 2);
+        assert_eq!(a.difference(&b), [1].into_iter().collect());
+        assert!(!a.is_disjoint(&b));
+        assert!(GrowableBitSet::new().is_subset(&b));
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::hash_map::{Entry, HashMap};
+use std::hash::Hash;
+
+/// A cycle in a sequence of states, where state `start + len` equals state `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The first step that is part of the cycle.
+    pub start: usize,
+    /// The number of steps before the cycle repeats.
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub const fn reduce(&self, n: u64) -> usize {
+        if n < self.start as u64 {
+            n as usize
+        } else {
+            self.start + ((n - self.start as u64) % self.len as u64) as usize
+        }
+    }
+
+    /// How many whole cycles are skipped when going from [`reduce(n)`](Self::reduce) to `n`.
+    ///
+    /// Useful for extrapolating values that accumulate by a fixed amount on each loop.
+    pub const fn full_cycles(&self, n: u64) -> u64 {
+        if n < self.start as u64 {
+            0
+        } else {
+            (n - self.start as u64) / self.len as u64
+        }
+    }
+}
+
+/// Find a cycle using Brent's algorithm, in O(start + len) steps and constant memory.
+///
+/// To get the state at step `n`, apply `step` to `initial` [`reduce(n)`](Cycle::reduce) times.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // Find the cycle length by racing a hare against a tortoise that teleports to it
+    // at each power of two.
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // Find the start by walking two states `len` apart until they meet.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+/// A detected cycle, along with every state up to the end of its first loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleHistory<T> {
+    pub cycle: Cycle,
+    pub history: Vec<T>,
+}
+
+impl<T> CycleHistory<T> {
+    /// The state at step `n`, which may be far beyond the recorded history.
+    pub fn state_at(&self, n: u64) -> &T {
+        &self.history[self.cycle.reduce(n)]
+    }
+}
+
+/// Find the first repeated state in `states`, comparing states by `key`.
+///
+/// Returns `None` if the iterator ends without repeating.
+pub fn find_cycle_by_key<I, K, F>(states: I, mut key: F) -> Option<CycleHistory<I::Item>>
+where
+    I: IntoIterator,
+    K: Hash + Eq,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    for (i, state) in states.into_iter().enumerate() {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                return Some(CycleHistory {
+                    cycle: Cycle {
+                        start,
+                        len: i - start,
+                    },
+                    history,
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+                history.push(state);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter;
+
+    // 0, 1, 2, 3, 4, 5, 6, 7, 3, 4, ...
+    fn step(&x: &u32) -> u32 {
+        if x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn brent() {
+        let cycle = find_cycle(0, step);
+        assert_eq!(cycle, Cycle { start: 3, len: 5 });
+        assert_eq!(find_cycle(5, step), Cycle { start: 0, len: 5 });
+        assert_eq!(find_cycle(1, |&x| x), Cycle { start: 0, len: 1 });
+    }
+
+    #[test]
+    fn by_key() {
+        let states = iter::successors(Some(0), |x| Some(step(x)));
+        let found = find_cycle_by_key(states, |&x| x).unwrap();
+        assert_eq!(found.cycle, Cycle { start: 3, len: 5 });
+        assert_eq!(found.history, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(*found.state_at(2), 2);
+        assert_eq!(*found.state_at(8), 3);
+        assert_eq!(*found.state_at(1_000_000_000_000), 5);
+
+        assert_eq!(find_cycle_by_key(0..10, |&x| x), None);
+    }
+
+    #[test]
+    fn extrapolate() {
+        let cycle = Cycle { start: 3, len: 5 };
+        assert_eq!(cycle.reduce(0), 0);
+        assert_eq!(cycle.reduce(7), 7);
+        assert_eq!(cycle.reduce(8), 3);
+        assert_eq!(cycle.full_cycles(2), 0);
+        assert_eq!(cycle.full_cycles(8), 1);
+        assert_eq!(cycle.full_cycles(1_000_000_000_000), 199_999_999_999);
+        assert_eq!(cycle.reduce(1_000_000_000_000), 5);
+    }
+}
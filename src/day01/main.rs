@@ -1,14 +1,183 @@
-use advent_2022::IteratorUtils;
+use std::env;
+
+use advent_2022::{IteratorUtils, StrUtils};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}: {text:?} is not a number of calories")]
+pub struct BadLine {
+    pub line: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Elves are numbered from 1, in the order they appear.
+    pub number: usize,
+    pub first_line: usize,
+    pub items: Vec<u32>,
+    /// The sum of `items`, which can be more than a `u32` holds.
+    pub calories: u64,
+}
+
+/// Splits an inventory list into elves at blank lines. Lines that aren't
+/// numbers are left out of their elf's inventory and returned separately.
+/// An elf's inventory is only empty if every line of its paragraph was such a line.
+pub fn parse_elves(text: &str) -> (Vec<Elf>, Vec<BadLine>) {
+    let mut elves = vec![];
+    let mut bad_lines = vec![];
+    for (number, paragraph) in (1..).zip(text.paragraphs()) {
+        let mut items = vec![];
+        for (line, text) in (paragraph.start_line..).zip(paragraph.lines()) {
+            match text.trim().parse() {
+                Ok(calories) => items.push(calories),
+                Err(_) => bad_lines.push(BadLine {
+                    line,
+                    text: text.to_owned(),
+                }),
+            }
+        }
+        elves.push(Elf {
+            number,
+            first_line: paragraph.start_line,
+            calories: items.iter().map(|&c| u64::from(c)).sum(),
+            items,
+        });
+    }
+    (elves, bad_lines)
+}
+
+/// The `k` elves carrying the most calories, each with its rank. Tied elves
+/// share the best rank of the tie and stay in input order.
+pub fn top_elves(elves: &[Elf], k: usize) -> Vec<(usize, &Elf)> {
+    let mut ranked: Vec<(usize, &Elf)> = Vec::with_capacity(k.min(elves.len()));
+    for (i, elf) in elves
+        .iter()
+        .max_k_by_key(k, |elf| elf.calories)
+        .into_iter()
+        .enumerate()
+    {
+        let rank = match ranked.last() {
+            Some(&(rank, prev)) if prev.calories == elf.calories => rank,
+            _ => i + 1,
+        };
+        ranked.push((rank, elf));
+    }
+    ranked
+}
 
 fn main() {
-    let max_elves = include_str!("input.txt")
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|c| c.parse::<u32>().unwrap()).sum::<u32>())
-        .max_n::<3>();
+    let (elves, bad_lines) = parse_elves(include_str!("input.txt"));
+    // How many elves to rank, or `all`
+    let k = match env::args().nth(1).as_deref() {
+        Some("all") => elves.len(),
+        Some(k) => k
+            .parse()
+            .expect("number of elves must be a number or `all`"),
+        None => 3,
+    };
+
+    for bad_line in &bad_lines {
+        println!("Skipped {bad_line}");
+    }
+    let Some(stats) = elves.iter().map(|elf| elf.calories).stats() else {
+        println!("No elves found");
+        return;
+    };
+
+    let top = top_elves(&elves, k);
+    println!("Top {} elves:", top.len());
+    for &(rank, elf) in &top {
+        println!(
+            "{rank:>4}. elf {} (from line {}): {} calories in {} items",
+            elf.number,
+            elf.first_line,
+            elf.calories,
+            elf.items.len(),
+        );
+    }
+    if let Some(&(_, last)) = top.last() {
+        let tied_with_last = |elf: &&Elf| elf.calories == last.calories;
+        let shown = top
+            .iter()
+            .map(|&(_, elf)| elf)
+            .filter(tied_with_last)
+            .count();
+        let tied = elves.iter().filter(tied_with_last).count() - shown;
+        if tied > 0 {
+            println!("  and {tied} more tied at {} calories", last.calories);
+        }
+    }
+    let top_total: u64 = top.iter().map(|(_, elf)| elf.calories).sum();
+    println!("Max {} elves total: {top_total}", top.len());
+
+    let empty = elves.iter().filter(|elf| elf.items.is_empty()).count();
+    println!("Elves: {}, with empty inventories: {empty}", stats.count);
+    println!(
+        "Calories per elf: min {}, max {}, mean {:.1}, median {:.1}, total {}",
+        stats.min, stats.max, stats.mean, stats.median, stats.sum,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let text = "1000\n2000\n\n4000\n\n\nlots\n5000\n6000\n\nmore\n\n7000\n8000\n9000\n\n4000\n";
+        let (elves, bad_lines) = parse_elves(text);
+        let summary: Vec<_> = elves
+            .iter()
+            .map(|elf| (elf.number, elf.first_line, elf.calories))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, 1, 3000),
+                (2, 4, 4000),
+                (3, 7, 11000),
+                (4, 11, 0),
+                (5, 13, 24000),
+                (6, 17, 4000)
+            ],
+        );
+        assert_eq!(
+            bad_lines,
+            [
+                BadLine {
+                    line: 7,
+                    text: "lots".to_owned()
+                },
+                BadLine {
+                    line: 11,
+                    text: "more".to_owned()
+                }
+            ],
+        );
 
-    println!("Max 3 elves: {:?}", max_elves);
+        let ranks: Vec<_> = top_elves(&elves, 4)
+            .into_iter()
+            .map(|(rank, elf)| (rank, elf.number))
+            .collect();
+        assert_eq!(ranks, [(1, 5), (2, 3), (3, 2), (3, 6)]);
+        assert_eq!(top_elves(&elves, 10).len(), 6);
+        assert_eq!(parse_elves(" \n"), (vec![], vec![]));
+    }
 
-    let max_elves_total: u32 = max_elves.into_iter().sum();
+    #[test]
+    fn trailing_blank_lines() {
+        let (elves, _) = parse_elves("\n1000\n2000\n\n3000\n\n\n");
+        let calories: Vec<_> = elves.iter().map(|elf| elf.calories).collect();
+        assert_eq!(calories, [3000, 3000]);
+        assert_eq!(elves[0].first_line, 2);
+        assert_eq!(parse_elves("1000\n2000\n\n3000\n\n").0.len(), 2);
+    }
 
-    println!("Max 3 elves total: {}", max_elves_total);
+    #[test]
+    fn totals_past_u32() {
+        let (elves, _) = parse_elves("4294967295\n4294967295\n\n1\n");
+        assert_eq!(elves[0].calories, 2 * u32::MAX as u64);
+        assert_eq!(top_elves(&elves, 1)[0].1.number, 1);
+    }
 }
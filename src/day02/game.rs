@@ -0,0 +1,301 @@
+use advent_2022::GrowableBitSet;
+use thiserror::Error;
+
+use super::{Hand, Outcome};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GameError {
+    #[error("line {line}: expected `SHAPE beats SHAPE...`, found {text:?}")]
+    BadLine { line: usize, text: String },
+    #[error("line {line}: unknown shape {name:?}")]
+    UnknownShape { line: usize, name: String },
+    #[error("shape {0:?} has more than one rule")]
+    DuplicateShape(String),
+    #[error("shape {0:?} beats itself")]
+    BeatsItself(String),
+    #[error("{0:?} and {1:?} both beat each other")]
+    Contradiction(String, String),
+    #[error("nothing decides between {0:?} and {1:?}")]
+    Undecided(String, String),
+    #[error("shape {0:?} beats {1} others, but must beat exactly half of them")]
+    Unbalanced(String, usize),
+    #[error("games need an odd number of at least 3 shapes, found {0}")]
+    BadSize(usize),
+}
+
+/// A shape in a [`Game`], identified by its position in the game's rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(usize);
+
+impl Shape {
+    /// The shape at `index` in a game's rules.
+    pub const fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub const fn index(self) -> usize {
+        self.0
+    }
+
+    /// Shapes score 1 for the first one in the rules, 2 for the next, and so on.
+    pub const fn score(self) -> u32 {
+        self.0 as u32 + 1
+    }
+}
+
+impl From<Hand> for Shape {
+    fn from(hand: Hand) -> Self {
+        Self(hand.score() as usize - 1)
+    }
+}
+
+/// A game like Rock-Paper-Scissors, where every pair of different shapes has
+/// a winner and every shape beats exactly half of the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    beats: Vec<GrowableBitSet>,
+}
+
+impl Game {
+    /// Rock, Paper, Scissors, in the same order as [`Hand`].
+    pub fn classic() -> Self {
+        Self::cyclic(["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    /// A game where each shape beats the half of the others listed just
+    /// before it, wrapping around.
+    pub fn cyclic<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Result<Self, GameError> {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        let n = names.len();
+        let beats = (0..n)
+            .map(|i| (1..=n / 2).map(|k| (i + n - k) % n).collect())
+            .collect();
+        Self::new(names, beats)
+    }
+
+    /// Parses one rule per line, e.g. `Rock beats Scissors Lizard`. Shapes
+    /// are numbered in the order of their rules. Blank lines and lines
+    /// starting with `#` are ignored.
+    pub fn from_rules(text: &str) -> Result<Self, GameError> {
+        let rules: Vec<_> = (1..)
+            .zip(text.lines())
+            .filter(|(_, text)| !text.trim().is_empty() && !text.trim().starts_with('#'))
+            .collect();
+        let mut names = vec![];
+        for &(line, text) in &rules {
+            match text.split_whitespace().collect::<Vec<_>>()[..] {
+                [name, "beats", ..] if !names.contains(&name) => names.push(name),
+                [name, "beats", ..] => return Err(GameError::DuplicateShape(name.to_owned())),
+                _ => {
+                    return Err(GameError::BadLine {
+                        line,
+                        text: text.to_owned(),
+                    })
+                }
+            }
+        }
+        let mut beats = vec![];
+        for &(line, text) in &rules {
+            let losers =
+                text.split_whitespace()
+                    .skip(2)
+                    .map(|name| {
+                        names.iter().position(|&n| n == name).ok_or_else(|| {
+                            GameError::UnknownShape {
+                                line,
+                                name: name.to_owned(),
+                            }
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+            beats.push(losers);
+        }
+        Self::new(names.into_iter().map(str::to_owned).collect(), beats)
+    }
+
+    fn new(names: Vec<String>, beats: Vec<GrowableBitSet>) -> Result<Self, GameError> {
+        let n = names.len();
+        if n < 3 || n % 2 != 1 {
+            return Err(GameError::BadSize(n));
+        }
+        for (i, name) in names.iter().enumerate() {
+            if beats[i].contains(i) {
+                return Err(GameError::BeatsItself(name.clone()));
+            }
+            for (j, other) in names.iter().enumerate().skip(i + 1) {
+                match (beats[i].contains(j), beats[j].contains(i)) {
+                    (true, true) => {
+                        return Err(GameError::Contradiction(name.clone(), other.clone()))
+                    }
+                    (false, false) => {
+                        return Err(GameError::Undecided(name.clone(), other.clone()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(i) = (0..n).find(|&i| beats[i].len() != n / 2) {
+            return Err(GameError::Unbalanced(names[i].clone(), beats[i].len()));
+        }
+        Ok(Self { names, beats })
+    }
+
+    pub fn size(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.size()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.names.iter().position(|n| n == name).map(Shape)
+    }
+
+    /// The equivalent of [`Hand::from_char`], for letters counting up from `base`.
+    pub fn shape_from_char(&self, c: char, base: char) -> Option<Shape> {
+        let index = (c as usize).checked_sub(base as usize)?;
+        (index < self.size()).then_some(Shape(index))
+    }
+
+    pub fn against(&self, mine: Shape, theirs: Shape) -> Outcome {
+        if self.beats[mine.0].contains(theirs.0) {
+            Outcome::Win
+        } else if mine == theirs {
+            Outcome::Draw
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// Every shape that `shape` gets the given outcome against.
+    pub fn opponents_for(
+        &self,
+        shape: Shape,
+        outcome: Outcome,
+    ) -> impl Iterator<Item = Shape> + '_ {
+        self.shapes()
+            .filter(move |&other| self.against(shape, other) == outcome)
+    }
+
+    /// The first shape, in rules order, that `shape` gets the given outcome against.
+    pub fn opponent_for(&self, shape: Shape, outcome: Outcome) -> Shape {
+        self.opponents_for(shape, outcome).next().unwrap()
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RPSLS: &str = "\
+        # Rock-Paper-Scissors-Lizard-Spock\n\
+        Rock beats Scissors Lizard\n\
+        Paper beats Rock Spock\n\
+        Scissors beats Paper Lizard\n\
+        \n\
+        Lizard beats Paper Spock\n\
+        Spock beats Rock Scissors\n";
+
+    #[test]
+    fn classic_matches_hand() {
+        let game = Game::classic();
+        let hands = [Hand::Rock, Hand::Paper, Hand::Scissors];
+        for mine in hands {
+            for theirs in hands {
+                assert_eq!(
+                    game.against(mine.into(), theirs.into()),
+                    mine.against(theirs)
+                );
+            }
+            for outcome in [Outcome::Win, Outcome::Draw, Outcome::Lose] {
+                assert_eq!(
+                    game.opponent_for(mine.into(), outcome),
+                    mine.opponent_for(outcome).into(),
+                );
+            }
+            assert_eq!(Shape::from(mine).score(), mine.score());
+        }
+        assert_eq!(
+            game,
+            Game::from_rules("Rock beats Scissors\nPaper beats Rock\nScissors beats Paper")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn rules_table() {
+        let game = Game::from_rules(RPSLS).unwrap();
+        assert_eq!(game.size(), 5);
+        let shape = |name| game.shape(name).unwrap();
+        assert_eq!(game.against(shape("Spock"), shape("Rock")), Outcome::Win);
+        assert_eq!(game.against(shape("Spock"), shape("Lizard")), Outcome::Lose);
+        assert_eq!(
+            game.against(shape("Lizard"), shape("Lizard")),
+            Outcome::Draw
+        );
+        let beaten: Vec<_> = game
+            .opponents_for(shape("Lizard"), Outcome::Win)
+            .map(|s| game.name(s))
+            .collect();
+        assert_eq!(beaten, ["Paper", "Spock"]);
+        assert_eq!(game.shape_from_char('E', 'A'), Some(shape("Spock")));
+        assert_eq!(game.shape_from_char('F', 'A'), None);
+    }
+
+    #[test]
+    fn cyclic_games() {
+        for n in [3, 5, 7, 101] {
+            let game = Game::cyclic((0..n).map(|i| i.to_string())).unwrap();
+            for shape in game.shapes() {
+                assert_eq!(game.opponents_for(shape, Outcome::Win).count(), n / 2);
+                assert_eq!(game.opponents_for(shape, Outcome::Lose).count(), n / 2);
+            }
+        }
+        assert_eq!(
+            Game::cyclic(["A", "B", "C", "D"]),
+            Err(GameError::BadSize(4))
+        );
+    }
+
+    #[test]
+    fn bad_rules() {
+        let err = |rules: &str| Game::from_rules(rules).unwrap_err();
+        assert_eq!(
+            err("Rock beats Scissors\nRock beats Paper"),
+            GameError::DuplicateShape("Rock".to_owned()),
+        );
+        assert_eq!(
+            err("Rock beats Scissors\nPaper beats Rock\nScissors beats Papr"),
+            GameError::UnknownShape {
+                line: 3,
+                name: "Papr".to_owned()
+            },
+        );
+        assert_eq!(
+            err("Rock beats Scissors\nPaper beats Rock\nScissors beats Paper Rock"),
+            GameError::Contradiction("Rock".to_owned(), "Scissors".to_owned()),
+        );
+        assert_eq!(err("Rock beats Rock"), GameError::BadSize(1),);
+        assert_eq!(
+            err("Rock crushes Scissors"),
+            GameError::BadLine {
+                line: 1,
+                text: "Rock crushes Scissors".to_owned()
+            },
+        );
+        let unbalanced = "A beats B C D E\nB beats C D\nC beats D E\nD beats E\nE beats B";
+        assert_eq!(err(unbalanced), GameError::Unbalanced("A".to_owned(), 4));
+    }
+}
@@ -0,0 +1,322 @@
+use thiserror::Error;
+
+use super::game::{Game, Shape};
+use super::Outcome;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GuideError {
+    #[error("line {line}: expected `KEY = VALUES`, found {text:?}")]
+    BadLine { line: usize, text: String },
+    #[error("line {line}: unknown key {key:?}")]
+    UnknownKey { line: usize, key: String },
+    #[error("line {line}: {key} needs {expected} values, found {found}")]
+    WrongCount {
+        line: usize,
+        key: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("line {line}: {value:?} is not a single letter")]
+    BadLetter { line: usize, value: String },
+    #[error("line {line}: letter {letter:?} is used twice")]
+    DuplicateLetter { line: usize, letter: char },
+    #[error("line {line}: {value:?} is not a valid score")]
+    BadScore { line: usize, value: String },
+    #[error("{key} must be given, since the standard letters can't name {shapes} shapes")]
+    MissingLetters { key: String, shapes: usize },
+}
+
+const fn outcome_index(outcome: Outcome) -> usize {
+    (outcome as i32 + 1) as usize
+}
+
+/// How to read a strategy guide's columns and score its rounds.
+///
+/// Letter and score lists follow the order of the game's shapes, and
+/// outcome lists go lose, draw, win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuideConfig {
+    theirs: Vec<char>,
+    mine: Vec<char>,
+    outcomes: [char; 3],
+    shape_scores: Vec<u32>,
+    outcome_scores: [u32; 3],
+}
+
+impl GuideConfig {
+    /// The puzzle's encoding: shapes are lettered from `A` and `X`, outcomes
+    /// are `X`, `Y`, `Z`, and scores are the same as [`Shape::score`] and
+    /// [`Outcome::score`].
+    ///
+    /// Letters stop at `Z`, so this only works for games with at most 3 shapes.
+    pub fn standard(game: &Game) -> Result<Self, GuideError> {
+        Self::parse("", game)
+    }
+
+    /// Parses `KEY = VALUES` lines, overriding the [standard](Self::standard)
+    /// config. The keys are `theirs`, `mine`, `outcomes`, `shape_scores` and
+    /// `outcome_scores`, and values are separated by whitespace. Blank lines
+    /// and lines starting with `#` are ignored.
+    ///
+    /// Games with too many shapes for the standard letters must give their own
+    /// `theirs` and `mine` letters.
+    pub fn parse(text: &str, game: &Game) -> Result<Self, GuideError> {
+        let standard_letters = |first: u8| {
+            (game.size() <= (b'Z' - first + 1) as usize).then(|| {
+                game.shapes()
+                    .map(|s| (first + s.index() as u8) as char)
+                    .collect()
+            })
+        };
+        let mut theirs = standard_letters(b'A');
+        let mut mine = standard_letters(b'X');
+        let mut config = Self {
+            theirs: vec![],
+            mine: vec![],
+            outcomes: ['X', 'Y', 'Z'],
+            shape_scores: game.shapes().map(Shape::score).collect(),
+            outcome_scores: Outcome::ALL.map(Outcome::score),
+        };
+        for (line, text) in (1..).zip(text.lines()) {
+            if text.trim().is_empty() || text.trim().starts_with('#') {
+                continue;
+            }
+            let Some((key, values)) = text.split_once('=') else {
+                return Err(GuideError::BadLine {
+                    line,
+                    text: text.to_owned(),
+                });
+            };
+            let key = key.trim();
+            let values: Vec<_> = values.split_whitespace().collect();
+            let expected = match key {
+                "theirs" | "mine" | "shape_scores" => game.size(),
+                "outcomes" | "outcome_scores" => Outcome::ALL.len(),
+                _ => {
+                    return Err(GuideError::UnknownKey {
+                        line,
+                        key: key.to_owned(),
+                    })
+                }
+            };
+            if values.len() != expected {
+                return Err(GuideError::WrongCount {
+                    line,
+                    key: key.to_owned(),
+                    expected,
+                    found: values.len(),
+                });
+            }
+            match key {
+                "theirs" => theirs = Some(parse_letters(line, &values)?),
+                "mine" => mine = Some(parse_letters(line, &values)?),
+                "outcomes" => config.outcomes = parse_letters(line, &values)?.try_into().unwrap(),
+                "shape_scores" => config.shape_scores = parse_scores(line, &values)?,
+                _ => config.outcome_scores = parse_scores(line, &values)?.try_into().unwrap(),
+            }
+        }
+        let missing = |key: &str| GuideError::MissingLetters {
+            key: key.to_owned(),
+            shapes: game.size(),
+        };
+        config.theirs = theirs.ok_or_else(|| missing("theirs"))?;
+        config.mine = mine.ok_or_else(|| missing("mine"))?;
+        Ok(config)
+    }
+
+    /// The shape a letter in the first column stands for.
+    pub fn their_shape(&self, letter: char) -> Option<Shape> {
+        self.theirs
+            .iter()
+            .position(|&l| l == letter)
+            .map(Shape::new)
+    }
+
+    /// The shape a letter in the second column stands for, when read as a shape.
+    pub fn my_shape(&self, letter: char) -> Option<Shape> {
+        self.mine.iter().position(|&l| l == letter).map(Shape::new)
+    }
+
+    /// The outcome a letter in the second column stands for, when read as an outcome.
+    pub fn outcome(&self, letter: char) -> Option<Outcome> {
+        let index = self.outcomes.iter().position(|&l| l == letter)?;
+        Some(Outcome::ALL[index])
+    }
+
+    pub fn shape_score(&self, shape: Shape) -> u32 {
+        self.shape_scores[shape.index()]
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        self.outcome_scores[outcome_index(outcome)]
+    }
+
+    /// The score for a round where I played `shape` and got `outcome`.
+    pub fn score(&self, shape: Shape, outcome: Outcome) -> u32 {
+        self.shape_score(shape) + self.outcome_score(outcome)
+    }
+}
+
+/// Reads the letter pairs of a strategy guide, one round per line, e.g. `A Y`.
+pub fn parse_rounds(text: &str) -> Vec<(char, char)> {
+    text.lines()
+        .map(|round| {
+            let mut round = round.chars();
+            let theirs = round.next().unwrap();
+            assert_eq!(round.next(), Some(' '));
+            let mine = round.next().unwrap();
+            (theirs, mine)
+        })
+        .collect()
+}
+
+fn parse_letters(line: usize, values: &[&str]) -> Result<Vec<char>, GuideError> {
+    let mut letters = vec![];
+    for &value in values {
+        let mut chars = value.chars();
+        let letter = match (chars.next(), chars.next()) {
+            (Some(letter), None) => letter,
+            _ => {
+                return Err(GuideError::BadLetter {
+                    line,
+                    value: value.to_owned(),
+                })
+            }
+        };
+        if letters.contains(&letter) {
+            return Err(GuideError::DuplicateLetter { line, letter });
+        }
+        letters.push(letter);
+    }
+    Ok(letters)
+}
+
+fn parse_scores(line: usize, values: &[&str]) -> Result<Vec<u32>, GuideError> {
+    values
+        .iter()
+        .map(|value| {
+            value.parse().map_err(|_| GuideError::BadScore {
+                line,
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_config() {
+        let game = Game::classic();
+        let config = GuideConfig::standard(&game).unwrap();
+        for (c, shape) in ('A'..='C').zip(game.shapes()) {
+            assert_eq!(config.their_shape(c), Some(shape));
+        }
+        for (c, shape) in ('X'..='Z').zip(game.shapes()) {
+            assert_eq!(config.my_shape(c), Some(shape));
+        }
+        for (c, outcome) in ('X'..='Z').zip(Outcome::ALL) {
+            assert_eq!(config.outcome(c), Some(outcome));
+            assert_eq!(config.outcome_score(outcome), outcome.score());
+        }
+        assert_eq!(config.their_shape('D'), None);
+        assert_eq!(config.score(game.shapes().last().unwrap(), Outcome::Win), 9);
+    }
+
+    #[test]
+    fn custom_config() {
+        let game = Game::classic();
+        let config = GuideConfig::parse(
+            "# Numbered columns\n\
+             theirs = 1 2 3\n\
+             mine=r p s\n\
+             \n\
+             outcome_scores = 0 1 2\n",
+            &game,
+        )
+        .unwrap();
+        let paper = game.shape("Paper").unwrap();
+        assert_eq!(config.their_shape('2'), Some(paper));
+        assert_eq!(config.their_shape('B'), None);
+        assert_eq!(config.my_shape('p'), Some(paper));
+        assert_eq!(config.outcome('Z'), Some(Outcome::Win));
+        assert_eq!(config.score(paper, Outcome::Draw), 3);
+    }
+
+    #[test]
+    fn larger_games() {
+        let game = Game::cyclic(["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap();
+        assert_eq!(
+            GuideConfig::standard(&game),
+            Err(GuideError::MissingLetters {
+                key: "mine".to_owned(),
+                shapes: 5
+            }),
+        );
+        let config = GuideConfig::parse("mine = V W X Y Z", &game).unwrap();
+        assert_eq!(config.their_shape('E'), game.shape("Lizard"));
+        assert_eq!(config.my_shape('Z'), game.shape("Lizard"));
+
+        let game = Game::cyclic((0..27).map(|i| i.to_string())).unwrap();
+        let mine = "mine = a b c d e f g h i j k l m n o p q r s t u v w x y z 0";
+        assert_eq!(
+            GuideConfig::parse(mine, &game),
+            Err(GuideError::MissingLetters {
+                key: "theirs".to_owned(),
+                shapes: 27
+            }),
+        );
+    }
+
+    #[test]
+    fn bad_configs() {
+        let game = Game::classic();
+        let err = |text| GuideConfig::parse(text, &game).unwrap_err();
+        assert_eq!(
+            err("mine X Y Z"),
+            GuideError::BadLine {
+                line: 1,
+                text: "mine X Y Z".to_owned()
+            },
+        );
+        assert_eq!(
+            err("\nyours = X Y Z"),
+            GuideError::UnknownKey {
+                line: 2,
+                key: "yours".to_owned()
+            },
+        );
+        assert_eq!(
+            err("mine = X Y"),
+            GuideError::WrongCount {
+                line: 1,
+                key: "mine".to_owned(),
+                expected: 3,
+                found: 2,
+            },
+        );
+        assert_eq!(
+            err("outcomes = L D WIN"),
+            GuideError::BadLetter {
+                line: 1,
+                value: "WIN".to_owned()
+            },
+        );
+        assert_eq!(
+            err("theirs = A B A"),
+            GuideError::DuplicateLetter {
+                line: 1,
+                letter: 'A'
+            },
+        );
+        assert_eq!(
+            err("shape_scores = 1 2 -3"),
+            GuideError::BadScore {
+                line: 1,
+                value: "-3".to_owned()
+            },
+        );
+    }
+}
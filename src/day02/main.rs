@@ -1,5 +1,18 @@
 #![feature(const_trait_impl)]
 
+pub mod game;
+pub mod guide;
+pub mod optimizer;
+pub mod tournament;
+
+use std::path::Path;
+use std::{env, fs};
+
+use game::Game;
+use guide::{parse_rounds, GuideConfig};
+use optimizer::Interpretation;
+use tournament::{Player, Tournament};
+
 #[derive(Debug, Clone, Copy, Eq)]
 pub enum Hand {
     Rock = 1,
@@ -79,6 +92,8 @@ pub enum Outcome {
 }
 
 impl Outcome {
+    pub const ALL: [Self; 3] = [Self::Lose, Self::Draw, Self::Win];
+
     pub const fn invert(self) -> Self {
         match self {
             Self::Win => Self::Lose,
@@ -106,30 +121,95 @@ impl Outcome {
 }
 
 fn main() {
+    let mut rules = None;
+    let mut guide = None;
+    let mut optimize = false;
+    let mut players = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // See `Game::from_rules` and `GuideConfig::parse` for the formats
+            "--rules" => rules = args.next(),
+            "--guide" => guide = args.next(),
+            "--optimize" => optimize = true,
+            // Every remaining argument is a strategy guide to play
+            "--tournament" => players.extend(args.by_ref()),
+            _ => panic!("unexpected argument {arg:?}"),
+        }
+    }
+    let game = match rules {
+        Some(path) => Game::from_rules(&fs::read_to_string(path).unwrap())
+            .unwrap_or_else(|err| panic!("invalid rules: {err}")),
+        None => Game::classic(),
+    };
+    let guide = match guide {
+        Some(path) => GuideConfig::parse(&fs::read_to_string(path).unwrap(), &game)
+            .unwrap_or_else(|err| panic!("invalid guide config: {err}")),
+        None => GuideConfig::standard(&game)
+            .unwrap_or_else(|err| panic!("these rules need a --guide config: {err}")),
+    };
+
+    let rounds = parse_rounds(include_str!("input.txt"));
     let mut total_score_p1 = 0;
     let mut total_score_p2 = 0;
 
-    for strat in include_str!("input.txt").lines() {
-        let mut strat = strat.chars();
-        let strat_a = strat.next().unwrap();
-        assert_eq!(strat.next(), Some(' '));
-        let strat_b = strat.next().unwrap();
-
-        let theirs = Hand::from_char(strat_a, 'A').unwrap();
+    for &(strat_a, strat_b) in &rounds {
+        let theirs = guide.their_shape(strat_a).unwrap();
         // Part 1
         {
-            let mine = Hand::from_char(strat_b, 'X').unwrap();
-            let outcome = mine.against(theirs);
-            total_score_p1 += mine.score() + outcome.score();
+            let mine = guide.my_shape(strat_b).unwrap();
+            let outcome = game.against(mine, theirs);
+            total_score_p1 += guide.score(mine, outcome);
         }
         // Part 2
         {
-            let outcome = Outcome::from_char(strat_b, 'X').unwrap();
-            let mine = theirs.opponent_for(outcome.invert());
-            total_score_p2 += mine.score() + outcome.score();
+            let outcome = guide.outcome(strat_b).unwrap();
+            let mine = game.opponent_for(theirs, outcome.invert());
+            total_score_p2 += guide.score(mine, outcome);
         }
     }
 
     println!("Total score, part 1: {total_score_p1}");
     println!("Total score, part 2: {total_score_p2}");
+
+    if optimize {
+        let response = optimizer::best_response(&game, &guide, &rounds);
+        println!("Best possible score: {}", response.score);
+        for (theirs, mine) in game.shapes().zip(response.shapes) {
+            println!("  play {} against {}", game.name(mine), game.name(theirs));
+        }
+
+        let (interpretation, score) = optimizer::best_interpretation(&game, &guide, &rounds);
+        println!("Best reading of the second column scores {score}:");
+        match interpretation {
+            Interpretation::Shapes(shapes) => {
+                for (letter, shape) in shapes {
+                    println!("  {letter} means play {}", game.name(shape));
+                }
+            }
+            Interpretation::Outcomes(outcomes) => {
+                for (letter, outcome) in outcomes {
+                    println!("  {letter} means {outcome:?}");
+                }
+            }
+        }
+    }
+
+    if !players.is_empty() {
+        let players: Vec<_> = players
+            .into_iter()
+            .map(|path| Player {
+                name: Path::new(&path)
+                    .file_stem()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+                moves: parse_rounds(&fs::read_to_string(&path).unwrap())
+                    .into_iter()
+                    .map(|(_, mine)| guide.my_shape(mine).unwrap())
+                    .collect(),
+            })
+            .collect();
+        print!("{}", Tournament::round_robin(&game, &players));
+    }
 }
@@ -0,0 +1,200 @@
+use std::cmp::Reverse;
+
+use super::game::{Game, Shape};
+use super::guide::GuideConfig;
+use super::Outcome;
+
+/// The best shape to play against each of the opponent's shapes, and what it
+/// would score over a whole guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// Indexed by the opponent's shape.
+    pub shapes: Vec<Shape>,
+    pub score: u32,
+}
+
+pub fn best_response(game: &Game, config: &GuideConfig, rounds: &[(char, char)]) -> Response {
+    let round_score = |mine, theirs| config.score(mine, game.against(mine, theirs));
+    let shapes: Vec<_> = game
+        .shapes()
+        .map(|theirs| {
+            game.shapes()
+                .min_by_key(|&mine| Reverse(round_score(mine, theirs)))
+                .unwrap()
+        })
+        .collect();
+    let score = rounds
+        .iter()
+        .map(|&(theirs, _)| {
+            let theirs = config.their_shape(theirs).unwrap();
+            round_score(shapes[theirs.index()], theirs)
+        })
+        .sum();
+    Response { shapes, score }
+}
+
+/// A way of reading each of the letters in a guide's second column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Interpretation {
+    Shapes(Vec<(char, Shape)>),
+    Outcomes(Vec<(char, Outcome)>),
+}
+
+/// Tries every way of reading the second column's letters as different
+/// shapes, or as different outcomes, and returns the one that scores best.
+///
+/// The search is exhaustive, so it's only quick for games with few shapes.
+pub fn best_interpretation(
+    game: &Game,
+    config: &GuideConfig,
+    rounds: &[(char, char)],
+) -> (Interpretation, u32) {
+    let mut letters: Vec<_> = rounds.iter().map(|&(_, mine)| mine).collect();
+    letters.sort();
+    letters.dedup();
+
+    // How many times each letter is played against each of their shapes
+    let mut counts = vec![vec![0; game.size()]; letters.len()];
+    for &(theirs, mine) in rounds {
+        let letter = letters.binary_search(&mine).unwrap();
+        counts[letter][config.their_shape(theirs).unwrap().index()] += 1;
+    }
+    let total = |counts: &[u32], round_score: &dyn Fn(Shape) -> u32| -> u32 {
+        game.shapes()
+            .map(|theirs| counts[theirs.index()] * round_score(theirs))
+            .sum()
+    };
+
+    let shape_values: Vec<Vec<_>> = counts
+        .iter()
+        .map(|counts| {
+            game.shapes()
+                .map(|mine| {
+                    total(counts, &|theirs| {
+                        config.score(mine, game.against(mine, theirs))
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    let outcome_values: Vec<Vec<_>> = counts
+        .iter()
+        .map(|counts| {
+            Outcome::ALL
+                .iter()
+                .map(|&outcome| {
+                    total(counts, &|theirs| {
+                        let mine = game.opponent_for(theirs, outcome.invert());
+                        config.score(mine, outcome)
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    let as_shapes = best_assignment(&shape_values).map(|(shapes, score)| {
+        let shapes = letters
+            .iter()
+            .copied()
+            .zip(shapes.into_iter().map(Shape::new));
+        (Interpretation::Shapes(shapes.collect()), score)
+    });
+    let as_outcomes = best_assignment(&outcome_values).map(|(outcomes, score)| {
+        let outcomes = letters
+            .iter()
+            .copied()
+            .zip(outcomes.into_iter().map(|o| Outcome::ALL[o]));
+        (Interpretation::Outcomes(outcomes.collect()), score)
+    });
+    match (as_shapes, as_outcomes) {
+        (Some(shapes), Some(outcomes)) if outcomes.1 > shapes.1 => outcomes,
+        (Some(shapes), _) => shapes,
+        (None, outcomes) => {
+            outcomes.expect("second column has more letters than the game has shapes")
+        }
+    }
+}
+
+/// Picks a different column for each row of `values`, maximizing the total
+/// of the picked values. Returns `None` if there are more rows than columns.
+fn best_assignment(values: &[Vec<u32>]) -> Option<(Vec<usize>, u32)> {
+    fn search(
+        values: &[Vec<u32>],
+        picked: &mut Vec<usize>,
+        total: u32,
+        best: &mut Option<(Vec<usize>, u32)>,
+    ) {
+        let Some(row) = values.get(picked.len()) else {
+            match best {
+                Some((_, best_total)) if *best_total >= total => {}
+                _ => *best = Some((picked.clone(), total)),
+            }
+            return;
+        };
+        for (column, &value) in row.iter().enumerate() {
+            if !picked.contains(&column) {
+                picked.push(column);
+                search(values, picked, total + value, best);
+                picked.pop();
+            }
+        }
+    }
+
+    let mut best = None;
+    search(values, &mut vec![], 0, &mut best);
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guide::parse_rounds;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn example_response() {
+        let game = Game::classic();
+        let config = GuideConfig::standard(&game).unwrap();
+        let response = best_response(&game, &config, &parse_rounds(EXAMPLE));
+        // Paper beats Rock, and so on
+        let names: Vec<_> = response.shapes.iter().map(|&s| game.name(s)).collect();
+        assert_eq!(names, ["Paper", "Scissors", "Rock"]);
+        assert_eq!(response.score, 8 + 9 + 7);
+    }
+
+    #[test]
+    fn example_interpretation() {
+        let game = Game::classic();
+        let config = GuideConfig::standard(&game).unwrap();
+        let shape = |name| game.shape(name).unwrap();
+        let (interpretation, score) = best_interpretation(&game, &config, &parse_rounds(EXAMPLE));
+        assert_eq!(
+            interpretation,
+            Interpretation::Shapes(vec![
+                ('X', shape("Scissors")),
+                ('Y', shape("Paper")),
+                ('Z', shape("Rock"))
+            ]),
+        );
+        assert_eq!(score, 8 + 9 + 7);
+
+        // A letter played against every shape can only win them all as an outcome
+        let config = GuideConfig::parse("outcome_scores = 0 30 60", &game).unwrap();
+        let rounds = parse_rounds("A X\nB X\nC X\n");
+        let (interpretation, score) = best_interpretation(&game, &config, &rounds);
+        assert_eq!(
+            interpretation,
+            Interpretation::Outcomes(vec![('X', Outcome::Win)])
+        );
+        assert_eq!(score, 2 + 3 + 1 + 3 * 60);
+    }
+
+    #[test]
+    fn assignments() {
+        let values = vec![vec![5, 1, 1], vec![5, 4, 1]];
+        assert_eq!(best_assignment(&values), Some((vec![0, 1], 9)));
+        assert_eq!(best_assignment(&[vec![1], vec![1]]), None);
+        assert_eq!(best_assignment(&[]), Some((vec![], 0)));
+    }
+}
@@ -0,0 +1,142 @@
+use std::cmp::Reverse;
+use std::fmt::{self, Display};
+
+use super::game::{Game, Shape};
+use super::Outcome;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub moves: Vec<Shape>,
+}
+
+/// The results of playing every pair of players against each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    pub names: Vec<String>,
+    /// `wins[i][j]` is how many rounds player `i` won against player `j`.
+    pub wins: Vec<Vec<u32>>,
+    pub draws: Vec<Vec<u32>>,
+    pub losses: Vec<Vec<u32>>,
+    /// Each player's total [`Outcome::score`] over all of their matches.
+    pub points: Vec<u32>,
+}
+
+impl Tournament {
+    /// Players play their moves in order, until the shorter of the two lists
+    /// runs out.
+    pub fn round_robin(game: &Game, players: &[Player]) -> Self {
+        let n = players.len();
+        let mut tournament = Self {
+            names: players.iter().map(|p| p.name.clone()).collect(),
+            wins: vec![vec![0; n]; n],
+            draws: vec![vec![0; n]; n],
+            losses: vec![vec![0; n]; n],
+            points: vec![0; n],
+        };
+        for (i, player) in players.iter().enumerate() {
+            for (j, opponent) in players.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                for (&mine, &theirs) in player.moves.iter().zip(&opponent.moves) {
+                    let outcome = game.against(mine, theirs);
+                    let matrix = match outcome {
+                        Outcome::Win => &mut tournament.wins,
+                        Outcome::Draw => &mut tournament.draws,
+                        Outcome::Lose => &mut tournament.losses,
+                    };
+                    matrix[i][j] += 1;
+                    tournament.points[i] += outcome.score();
+                }
+            }
+        }
+        tournament
+    }
+
+    /// Player indices, from most to fewest points.
+    pub fn standings(&self) -> Vec<usize> {
+        let mut standings: Vec<_> = (0..self.names.len()).collect();
+        standings.sort_by_key(|&i| Reverse(self.points[i]));
+        standings
+    }
+
+    fn write_matrix(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        title: &str,
+        matrix: &[Vec<u32>],
+    ) -> fmt::Result {
+        let width = self.names.iter().map(String::len).max().unwrap_or(0).max(5);
+        write!(f, "{title:width$}")?;
+        for name in &self.names {
+            write!(f, " {name:>width$}")?;
+        }
+        writeln!(f)?;
+        for (i, (name, row)) in self.names.iter().zip(matrix).enumerate() {
+            write!(f, "{name:width$}")?;
+            for (j, count) in row.iter().enumerate() {
+                if i == j {
+                    write!(f, " {:>width$}", "-")?;
+                } else {
+                    write!(f, " {count:>width$}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_matrix(f, "Wins", &self.wins)?;
+        writeln!(f)?;
+        self.write_matrix(f, "Draws", &self.draws)?;
+        writeln!(f)?;
+        self.write_matrix(f, "Losses", &self.losses)?;
+        writeln!(f)?;
+        for (place, i) in (1..).zip(self.standings()) {
+            writeln!(f, "{place}. {} ({} points)", self.names[i], self.points[i])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_players() {
+        let game = Game::classic();
+        let shape = |name: &str| game.shape(name).unwrap();
+        let player = |name: &str, moves: &[&str]| Player {
+            name: name.to_owned(),
+            moves: moves.iter().map(|&m| shape(m)).collect(),
+        };
+        let players = [
+            player("rocky", &["Rock", "Rock", "Rock"]),
+            player("papers", &["Paper", "Paper"]),
+            player("mixed", &["Rock", "Paper", "Scissors"]),
+        ];
+        let tournament = Tournament::round_robin(&game, &players);
+
+        assert_eq!(tournament.wins, [[0, 0, 1], [2, 0, 1], [1, 0, 0]]);
+        assert_eq!(tournament.draws, [[0, 0, 1], [0, 0, 1], [1, 1, 0]]);
+        assert_eq!(tournament.losses, [[0, 2, 1], [0, 0, 0], [1, 1, 0]]);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(tournament.wins[i][j], tournament.losses[j][i]);
+            }
+        }
+        assert_eq!(tournament.points, [6 + 3, 18 + 3, 6 + 6]);
+        assert_eq!(tournament.standings(), [1, 2, 0]);
+
+        let table = tournament.to_string();
+        assert!(table.starts_with("Wins    rocky papers  mixed\nrocky       -      0      1\n"));
+        assert!(
+            table.ends_with("1. papers (21 points)\n2. mixed (12 points)\n3. rocky (9 points)\n")
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::Item;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AlphabetError {
+    #[error("symbol {0:?} appears more than once")]
+    DuplicateSymbol(char),
+    #[error("{0} symbols given, at most {max} are supported", max = Alphabet::MAX_SYMBOLS)]
+    TooManySymbols(usize),
+    #[error("range {start:?}-{end:?} is backwards")]
+    BadRange { start: char, end: char },
+    #[error("line {line}: expected a symbol followed by its priority, found {text:?}")]
+    BadLine { line: usize, text: String },
+}
+
+/// Maps the symbols found in rucksacks to items and their priorities.
+///
+/// Items are numbered from 0 in the order their symbols were given, which is
+/// also the order item sets iterate in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: Vec<char>,
+    priorities: Vec<u32>,
+    ascii: [Option<Item>; 128],
+    other: HashMap<char, Item>,
+}
+
+impl Alphabet {
+    pub const MAX_SYMBOLS: usize = 256;
+
+    /// The puzzle's alphabet: `a-z` have priorities 1-26 and `A-Z` 27-52.
+    pub fn standard() -> Self {
+        Self::from_spec("a-zA-Z").unwrap()
+    }
+
+    pub fn from_symbols(
+        symbols: impl IntoIterator<Item = (char, u32)>,
+    ) -> Result<Self, AlphabetError> {
+        let symbols: Vec<_> = symbols.into_iter().collect();
+        if symbols.len() > Self::MAX_SYMBOLS {
+            return Err(AlphabetError::TooManySymbols(symbols.len()));
+        }
+        let mut alphabet = Self {
+            symbols: vec![],
+            priorities: vec![],
+            ascii: [None; 128],
+            other: HashMap::new(),
+        };
+        for (symbol, priority) in symbols {
+            let index = alphabet.symbols.len();
+            if alphabet.item(symbol).is_some() {
+                return Err(AlphabetError::DuplicateSymbol(symbol));
+            }
+            let item = Item(index as u8);
+            match alphabet.ascii.get_mut(symbol as usize) {
+                Some(slot) => *slot = Some(item),
+                None => {
+                    alphabet.other.insert(symbol, item);
+                }
+            }
+            alphabet.symbols.push(symbol);
+            alphabet.priorities.push(priority);
+        }
+        Ok(alphabet)
+    }
+
+    /// Parses a list of symbols and `x-y` ranges, e.g. `a-zA-Z0-9`.
+    ///
+    /// Priorities count up from 1 in the order symbols are listed. A `-` at
+    /// the start or end of the spec stands for itself.
+    pub fn from_spec(spec: &str) -> Result<Self, AlphabetError> {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut symbols = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            if i + 2 < chars.len() && chars[i + 1] == '-' {
+                let (start, end) = (chars[i], chars[i + 2]);
+                if start > end {
+                    return Err(AlphabetError::BadRange { start, end });
+                }
+                symbols.extend(start..=end);
+                i += 3;
+            } else {
+                symbols.push(chars[i]);
+                i += 1;
+            }
+        }
+        Self::from_symbols(symbols.into_iter().zip(1..))
+    }
+
+    /// Parses a table with one symbol per line, followed by whitespace and
+    /// its priority. Blank lines are ignored.
+    pub fn from_table(text: &str) -> Result<Self, AlphabetError> {
+        let mut symbols = vec![];
+        for (line, text) in (1..).zip(text.lines()) {
+            if text.trim().is_empty() {
+                continue;
+            }
+            let mut chars = text.chars();
+            let symbol = chars.next().unwrap();
+            let priority = chars.as_str().trim();
+            match priority.parse() {
+                Ok(priority) if chars.as_str().starts_with(char::is_whitespace) => {
+                    symbols.push((symbol, priority))
+                }
+                _ => {
+                    return Err(AlphabetError::BadLine {
+                        line,
+                        text: text.to_owned(),
+                    })
+                }
+            }
+        }
+        Self::from_symbols(symbols)
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// The number of 64-bit words an item set needs to hold every item.
+    pub fn words(&self) -> usize {
+        (self.len().max(1) - 1) / 64 + 1
+    }
+
+    pub fn item(&self, symbol: char) -> Option<Item> {
+        match self.ascii.get(symbol as usize) {
+            Some(item) => *item,
+            None => self.other.get(&symbol).copied(),
+        }
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = Item> {
+        (0..self.len()).map(|index| Item(index as u8))
+    }
+
+    pub fn symbol(&self, item: Item) -> char {
+        self.symbols[item.index()]
+    }
+
+    pub fn priority(&self, item: Item) -> u32 {
+        self.priorities[item.index()]
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_alphabet() {
+        let alphabet = Alphabet::standard();
+        assert_eq!(alphabet.len(), 52);
+        assert_eq!(alphabet.words(), 1);
+        for c in "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".chars() {
+            let item = alphabet.item(c).unwrap();
+            assert_eq!(Item::from_char(c), Some(item));
+            assert_eq!(alphabet.symbol(item), c);
+            assert_eq!(alphabet.priority(item), item.priority());
+        }
+        assert_eq!(alphabet.item('0'), None);
+    }
+
+    #[test]
+    fn spec_alphabets() {
+        let alphabet = Alphabet::from_spec("0-9-é_").unwrap();
+        let symbols: String = alphabet.items().map(|i| alphabet.symbol(i)).collect();
+        assert_eq!(symbols, "0123456789-é_");
+        assert_eq!(alphabet.priority(alphabet.item('é').unwrap()), 12);
+
+        assert_eq!(
+            Alphabet::from_spec("z-a"),
+            Err(AlphabetError::BadRange {
+                start: 'z',
+                end: 'a'
+            }),
+        );
+        assert_eq!(
+            Alphabet::from_spec("a-zq"),
+            Err(AlphabetError::DuplicateSymbol('q')),
+        );
+
+        let full = Alphabet::from_spec("\u{100}-\u{1ff}").unwrap();
+        assert_eq!(full.words(), 4);
+        assert_eq!(full.priority(full.item('\u{1ff}').unwrap()), 256);
+        assert_eq!(
+            Alphabet::from_spec("\u{100}-\u{200}"),
+            Err(AlphabetError::TooManySymbols(257)),
+        );
+    }
+
+    #[test]
+    fn table_alphabets() {
+        let alphabet = Alphabet::from_table("# 100\n\n  5\n7 3\r\n").unwrap();
+        assert_eq!(alphabet.len(), 3);
+        assert_eq!(alphabet.priority(alphabet.item('#').unwrap()), 100);
+        assert_eq!(alphabet.priority(alphabet.item(' ').unwrap()), 5);
+        assert_eq!(alphabet.priority(alphabet.item('7').unwrap()), 3);
+
+        for (text, line) in [("a 1\nb\n", 2), ("ab 1", 1), ("a one", 1)] {
+            assert_eq!(
+                Alphabet::from_table(text),
+                Err(AlphabetError::BadLine {
+                    line,
+                    text: text.lines().nth(line - 1).unwrap().to_owned(),
+                }),
+            );
+        }
+    }
+}
@@ -1,126 +1,144 @@
 #![feature(exact_size_is_empty)]
 #![feature(const_trait_impl)]
 #![feature(const_mut_refs)]
-#![feature(iter_array_chunks)]
+
+pub mod alphabet;
 
 use std::fmt::{self, Debug};
-use std::num::NonZeroU8;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::{env, fs};
+
+use advent_2022::BitSet;
+use thiserror::Error;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub struct Item(NonZeroU8);
+use alphabet::Alphabet;
+
+/// An item, identified by its index in an [`Alphabet`].
+///
+/// The `const` conversions use the puzzle's standard alphabet; items from
+/// other alphabets go through [`Alphabet::symbol`] and [`Alphabet::priority`].
+/// Its `Debug` output is just the index, since the symbol depends on the alphabet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item(u8);
 
 impl Item {
     pub const MIN_PRIORITY: u8 = 1;
     pub const MAX_PRIORITY: u8 = 52;
 
     pub const fn from_char(c: char) -> Option<Self> {
-        Some(Self(unsafe {
-            NonZeroU8::new_unchecked(match c {
-                'a'..='z' => c as u8 - b'a' + 1,
-                'A'..='Z' => c as u8 - b'A' + 27,
-                _ => return None,
-            })
+        Some(Self(match c {
+            'a'..='z' => c as u8 - b'a',
+            'A'..='Z' => c as u8 - b'A' + 26,
+            _ => return None,
         }))
     }
 
     pub const fn from_index(p: u8) -> Option<Self> {
         if p < Self::MAX_PRIORITY {
-            Some(Self(unsafe { NonZeroU8::new_unchecked(p + 1) }))
+            Some(Self(p))
         } else {
             None
         }
     }
 
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
     pub const fn to_char(self) -> char {
-        (match self.0.get() {
-            c @ 1..=26 => c - 1 + b'a',
-            c @ 27..=52 => c - 27 + b'A',
-            _ => unreachable!(),
+        (match self.0 {
+            c @ 0..=25 => c + b'a',
+            c @ 26..=51 => c - 26 + b'A',
+            _ => panic!("item is not in the standard alphabet"),
         }) as char
     }
 
     pub const fn priority(self) -> u32 {
-        self.0.get() as u32
-    }
-}
-
-impl Debug for Item {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Item").field(&self.to_char()).finish()
+        self.0 as u32 + 1
     }
 }
 
 #[derive(PartialEq, Eq, Default)]
-pub struct ItemSet(u64);
+pub struct ItemSet<const WORDS: usize = 1>(BitSet<WORDS>);
 
-impl ItemSet {
+impl<const WORDS: usize> ItemSet<WORDS> {
     pub const fn new() -> Self {
-        Self(0)
+        Self(BitSet::new())
     }
 
     pub const fn len(&self) -> usize {
-        self.0.count_ones() as usize
+        self.0.len()
     }
 
     pub const fn is_empty(&self) -> bool {
-        self.0 == 0
+        self.0.is_empty()
     }
 
-    const fn item_to_flag(item: Item) -> u64 {
-        1 << (item.0.get() as u64 - 1)
+    /// How many items of an alphabet fit in this size of set.
+    pub const CAPACITY: usize = BitSet::<WORDS>::CAPACITY;
+
+    const fn index_to_item(index: Option<usize>) -> Option<Item> {
+        match index {
+            Some(index) => Some(Item(index as u8)),
+            None => None,
+        }
     }
 
     pub const fn contains(&self, item: Item) -> bool {
-        self.0 & Self::item_to_flag(item) != 0
+        self.0.contains(item.index())
     }
 
     pub const fn add(&mut self, item: Item) {
-        self.0 |= Self::item_to_flag(item);
+        self.0.insert(item.index());
     }
 
     pub const fn remove(&mut self, item: Item) {
-        self.0 &= !Self::item_to_flag(item);
+        self.0.remove(item.index());
     }
 
     pub const fn union(&self, other: &Self) -> Self {
-        Self(self.0 | other.0)
+        Self(self.0.union(&other.0))
     }
 
     pub const fn intersection(&self, other: &Self) -> Self {
-        Self(self.0 & other.0)
+        Self(self.0.intersection(&other.0))
     }
 
     pub const fn first(&self) -> Option<Item> {
-        if self.is_empty() {
-            None
-        } else {
-            let index = self.0.trailing_zeros();
-            Item::from_index(index as u8)
-        }
+        Self::index_to_item(self.0.first())
     }
 
     pub const fn pop_first(&mut self) -> Option<Item> {
-        if self.is_empty() {
-            None
-        } else {
-            let index = self.0.trailing_zeros();
-            self.0 &= !(1 << index as u64);
-            Item::from_index(index as u8)
-        }
+        Self::index_to_item(self.0.pop_first())
     }
 
-    pub const fn iter(&self) -> ItemSetIter {
+    pub const fn iter(&self) -> ItemSetIter<WORDS> {
         ItemSetIter(self.clone())
     }
+
+    /// Items that appear in at least `min` of the given sets.
+    pub fn in_at_least<'a>(sets: impl IntoIterator<Item = &'a Self>, min: usize) -> Self {
+        assert!(min > 0, "every item is in at least 0 sets");
+        // seen[i] holds the items found in more than i of the sets so far
+        let mut seen = vec![Self::new(); min];
+        for set in sets {
+            for i in (1..min).rev() {
+                seen[i] = seen[i].union(&seen[i - 1].intersection(set));
+            }
+            seen[0] = seen[0].union(set);
+        }
+        seen.pop().unwrap()
+    }
 }
 
-impl const Clone for ItemSet {
+impl<const WORDS: usize> const Clone for ItemSet<WORDS> {
     fn clone(&self) -> Self {
         Self(self.0)
     }
 }
 
-impl FromIterator<Item> for ItemSet {
+impl<const WORDS: usize> FromIterator<Item> for ItemSet<WORDS> {
     fn from_iter<T: IntoIterator<Item = Item>>(iter: T) -> Self {
         let mut set = Self::new();
         for item in iter {
@@ -130,7 +148,7 @@ impl FromIterator<Item> for ItemSet {
     }
 }
 
-impl Extend<Item> for ItemSet {
+impl<const WORDS: usize> Extend<Item> for ItemSet<WORDS> {
     fn extend<T: IntoIterator<Item = Item>>(&mut self, iter: T) {
         for item in iter {
             self.add(item);
@@ -138,20 +156,20 @@ impl Extend<Item> for ItemSet {
     }
 }
 
-impl IntoIterator for ItemSet {
+impl<const WORDS: usize> IntoIterator for ItemSet<WORDS> {
     type Item = Item;
 
-    type IntoIter = ItemSetIter;
+    type IntoIter = ItemSetIter<WORDS>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-impl IntoIterator for &ItemSet {
+impl<const WORDS: usize> IntoIterator for &ItemSet<WORDS> {
     type Item = Item;
 
-    type IntoIter = ItemSetIter;
+    type IntoIter = ItemSetIter<WORDS>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -159,15 +177,15 @@ impl IntoIterator for &ItemSet {
 }
 
 #[derive(Clone)]
-pub struct ItemSetIter(ItemSet);
+pub struct ItemSetIter<const WORDS: usize = 1>(ItemSet<WORDS>);
 
-impl ItemSetIter {
-    pub const fn into_remaining(self) -> ItemSet {
+impl<const WORDS: usize> ItemSetIter<WORDS> {
+    pub const fn into_remaining(self) -> ItemSet<WORDS> {
         self.0
     }
 }
 
-impl ExactSizeIterator for ItemSetIter {
+impl<const WORDS: usize> ExactSizeIterator for ItemSetIter<WORDS> {
     fn len(&self) -> usize {
         self.0.len()
     }
@@ -177,7 +195,7 @@ impl ExactSizeIterator for ItemSetIter {
     }
 }
 
-impl Iterator for ItemSetIter {
+impl<const WORDS: usize> Iterator for ItemSetIter<WORDS> {
     type Item = Item;
 
     fn next(&mut self) -> Option<Item> {
@@ -190,37 +208,320 @@ impl Iterator for ItemSetIter {
     }
 }
 
-impl Debug for ItemSet {
+impl<const WORDS: usize> Debug for ItemSet<WORDS> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self).finish()
     }
 }
 
-fn main() {
-    let mut mispacked_sum = 0;
-    let mut badges_sum = 0;
-
-    for group in include_str!("input.txt").lines().array_chunks::<3>() {
-        let group_items = group
-            .map(|rucksack| {
-                let (left, right) = rucksack.split_at(rucksack.len() / 2);
-                let left: ItemSet = left.chars().map(|c| Item::from_char(c).unwrap()).collect();
-                let right: ItemSet = right.chars().map(|c| Item::from_char(c).unwrap()).collect();
-                for item in left.intersection(&right) {
-                    mispacked_sum += item.priority();
-                }
-                left.union(&right)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RucksackError {
+    #[error("odd number of items ({0})")]
+    OddLength(usize),
+    #[error("unknown item {symbol:?} at column {column}")]
+    UnknownItem { symbol: char, column: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack<const WORDS: usize = 1> {
+    pub left: ItemSet<WORDS>,
+    pub right: ItemSet<WORDS>,
+}
+
+impl<const WORDS: usize> Rucksack<WORDS> {
+    pub fn parse(alphabet: &Alphabet, line: &str) -> Result<Self, RucksackError> {
+        let items = line
+            .chars()
+            .zip(1..)
+            .map(|(symbol, column)| {
+                alphabet
+                    .item(symbol)
+                    .ok_or(RucksackError::UnknownItem { symbol, column })
             })
+            .collect::<Result<Vec<_>, _>>()?;
+        if items.len() % 2 != 0 {
+            return Err(RucksackError::OddLength(items.len()));
+        }
+        let (left, right) = items.split_at(items.len() / 2);
+        Ok(Self {
+            left: left.iter().copied().collect(),
+            right: right.iter().copied().collect(),
+        })
+    }
+
+    /// Items that were packed into both compartments.
+    pub fn mispacked(&self) -> ItemSet<WORDS> {
+        self.left.intersection(&self.right)
+    }
+
+    pub fn items(&self) -> ItemSet<WORDS> {
+        self.left.union(&self.right)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GroupError<const WORDS: usize = 1> {
+    #[error("only {found} of {expected} rucksacks")]
+    Incomplete { found: usize, expected: usize },
+    #[error("contains a malformed rucksack")]
+    Malformed,
+    #[error("no common item")]
+    NoBadge,
+    #[error("{} common items", .0.len())]
+    SeveralBadges(ItemSet<WORDS>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group<const WORDS: usize = 1> {
+    pub lines: RangeInclusive<usize>,
+    pub badge: Result<Item, GroupError<WORDS>>,
+}
+
+/// How rucksacks are split into groups, and what makes an item a group's badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grouping {
+    pub size: usize,
+    /// How many of a group's rucksacks must hold an item for it to be the badge.
+    pub min_common: usize,
+    /// Whether every run of `size` consecutive rucksacks is a group, instead
+    /// of splitting them into separate runs.
+    pub sliding: bool,
+}
+
+impl Grouping {
+    pub const fn new(size: usize) -> Self {
+        assert!(size > 0, "groups must have at least one rucksack");
+        Self {
+            size,
+            min_common: size,
+            sliding: false,
+        }
+    }
+
+    pub const fn sliding(self) -> Self {
+        Self {
+            sliding: true,
+            ..self
+        }
+    }
+
+    pub const fn at_least(self, min_common: usize) -> Self {
+        assert!(
+            min_common > 0 && min_common <= self.size,
+            "badges must be common to between 1 and all of a group's rucksacks",
+        );
+        Self { min_common, ..self }
+    }
+
+    fn find_badge<const WORDS: usize>(
+        &self,
+        rucksacks: &[Result<Rucksack<WORDS>, RucksackError>],
+    ) -> Result<Item, GroupError<WORDS>> {
+        if rucksacks.len() < self.size {
+            return Err(GroupError::Incomplete {
+                found: rucksacks.len(),
+                expected: self.size,
+            });
+        }
+        let items = rucksacks
+            .iter()
+            .map(|rucksack| rucksack.as_ref().map(Rucksack::items))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| GroupError::Malformed)?;
+        let common = ItemSet::in_at_least(&items, self.min_common);
+        match common.len() {
+            0 => Err(GroupError::NoBadge),
+            1 => Ok(common.first().unwrap()),
+            _ => Err(GroupError::SeveralBadges(common)),
+        }
+    }
+}
+
+impl Default for Grouping {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Every rucksack and group in a list, including the ones that can't be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<const WORDS: usize = 1> {
+    /// One entry per line, starting from line 1.
+    pub rucksacks: Vec<Result<Rucksack<WORDS>, RucksackError>>,
+    pub groups: Vec<Group<WORDS>>,
+}
+
+impl<const WORDS: usize> Report<WORDS> {
+    pub fn new(alphabet: &Alphabet, input: &str, grouping: Grouping) -> Self {
+        let rucksacks: Vec<_> = input
+            .lines()
+            .map(|line| Rucksack::parse(alphabet, line))
+            .collect();
+        let groups: Vec<_> = if grouping.sliding && rucksacks.len() >= grouping.size {
+            (1..).zip(rucksacks.windows(grouping.size)).collect()
+        } else {
+            (1..)
+                .step_by(grouping.size)
+                .zip(rucksacks.chunks(grouping.size))
+                .collect()
+        };
+        let groups = groups
             .into_iter()
-            .reduce(|a, b| a.intersection(&b))
-            .unwrap();
-        assert_eq!(group_items.len(), 1);
-        let badge = group_items.first().unwrap();
-        badges_sum += badge.priority();
+            .map(|(first_line, group)| Group {
+                lines: first_line..=first_line + group.len() - 1,
+                badge: grouping.find_badge(group),
+            })
+            .collect();
+        Self { rucksacks, groups }
+    }
+
+    pub fn mispacked_sum(&self, alphabet: &Alphabet) -> u32 {
+        self.rucksacks
+            .iter()
+            .flatten()
+            .flat_map(Rucksack::mispacked)
+            .map(|item| alphabet.priority(item))
+            .sum()
+    }
+
+    pub fn badges_sum(&self, alphabet: &Alphabet) -> u32 {
+        self.groups
+            .iter()
+            .filter_map(|group| group.badge.as_ref().ok())
+            .map(|&item| alphabet.priority(item))
+            .sum()
+    }
+
+    /// The number of malformed rucksacks plus the number of groups without
+    /// exactly one badge.
+    pub fn problems(&self) -> usize {
+        let rucksacks = self.rucksacks.iter().filter(|r| r.is_err()).count();
+        let groups = self.groups.iter().filter(|g| g.badge.is_err()).count();
+        rucksacks + groups
+    }
+}
+
+fn describe_items<const WORDS: usize>(alphabet: &Alphabet, items: &ItemSet<WORDS>) -> String {
+    let items: Vec<_> = items
+        .iter()
+        .map(|item| format!("{} ({})", alphabet.symbol(item), alphabet.priority(item)))
+        .collect();
+    items.join(", ")
+}
+
+fn print_report<const WORDS: usize>(alphabet: &Alphabet, report: &Report<WORDS>) {
+    for (line, rucksack) in (1..).zip(&report.rucksacks) {
+        match rucksack {
+            Ok(rucksack) if rucksack.mispacked().is_empty() => {
+                println!("Line {line}: nothing mispacked")
+            }
+            Ok(rucksack) => println!(
+                "Line {line}: mispacked {}",
+                describe_items(alphabet, &rucksack.mispacked()),
+            ),
+            Err(err) => println!("Line {line}: {err}"),
+        }
+    }
+    for group in &report.groups {
+        let (first, last) = (group.lines.start(), group.lines.end());
+        let lines = if first == last {
+            format!("line {first}")
+        } else {
+            format!("lines {first}-{last}")
+        };
+        match &group.badge {
+            Ok(badge) => println!(
+                "Group at {lines}: badge {} ({})",
+                alphabet.symbol(*badge),
+                alphabet.priority(*badge),
+            ),
+            Err(err @ GroupError::SeveralBadges(common)) => println!(
+                "Group at {lines}: {err}: {}",
+                describe_items(alphabet, common),
+            ),
+            Err(err) => println!("Group at {lines}: {err}"),
+        }
     }
+}
 
-    println!("Total mispacked priority: {mispacked_sum}");
-    println!("Total badge priority: {badges_sum}");
+struct Options {
+    verbose: bool,
+    grouping: Grouping,
+    alphabet: Alphabet,
+}
+
+impl Options {
+    /// Parses `[--report] [--group SIZE] [--at-least MIN] [--sliding] [ALPHABET]`,
+    /// where the alphabet is either the path of a table or a spec like `a-zA-Z0-9`.
+    fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut verbose = false;
+        let mut size = 3;
+        let mut min_common = None;
+        let mut sliding = false;
+        let mut alphabet = None;
+        let number = |name: &str, value: Option<String>| -> usize {
+            value
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_else(|| panic!("{name} must be followed by a number"))
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--report" => verbose = true,
+                "--group" => size = number("--group", args.next()),
+                "--at-least" => min_common = Some(number("--at-least", args.next())),
+                "--sliding" => sliding = true,
+                _ => alphabet = Some(arg),
+            }
+        }
+
+        let mut grouping = Grouping::new(size).at_least(min_common.unwrap_or(size));
+        if sliding {
+            grouping = grouping.sliding();
+        }
+        let alphabet = match alphabet {
+            Some(path) if Path::new(&path).is_file() => {
+                Alphabet::from_table(&fs::read_to_string(path).unwrap())
+            }
+            Some(spec) => Alphabet::from_spec(&spec),
+            None => Ok(Alphabet::standard()),
+        }
+        .unwrap_or_else(|err| panic!("invalid alphabet: {err}"));
+
+        Self {
+            verbose,
+            grouping,
+            alphabet,
+        }
+    }
+
+    fn run<const WORDS: usize>(&self, input: &str) {
+        let alphabet = &self.alphabet;
+        let report = Report::<WORDS>::new(alphabet, input, self.grouping);
+        if self.verbose {
+            print_report(alphabet, &report);
+        }
+
+        println!(
+            "Total mispacked priority: {}",
+            report.mispacked_sum(alphabet)
+        );
+        println!("Total badge priority: {}", report.badges_sum(alphabet));
+        let problems = report.problems();
+        if problems > 0 && !self.verbose {
+            println!("Skipped {problems} problems, run with --report for details");
+        }
+    }
+}
+
+fn main() {
+    let options = Options::from_args(env::args().skip(1));
+    let input = include_str!("input.txt");
+    match options.alphabet.words() {
+        1 => options.run::<1>(input),
+        2 => options.run::<2>(input),
+        3 => options.run::<3>(input),
+        _ => options.run::<4>(input),
+    }
 }
 
 #[cfg(test)]
@@ -237,7 +538,7 @@ mod tests {
             assert_eq!(item.to_char(), c);
             assert_eq!(item.priority() as usize, i + 1);
             assert_eq!(Item::from_index(i as u8), Some(item));
-            assert_eq!(format!("{item:?}"), format!("Item({c:?})"));
+            assert_eq!(format!("{item:?}"), format!("Item({i})"));
         }
     }
 
@@ -250,4 +551,107 @@ mod tests {
             assert_eq!(Item::from_index(p), None);
         }
     }
+
+    #[test]
+    fn wide_item_sets() {
+        let alphabet = Alphabet::from_spec("\u{100}-\u{1ff}").unwrap();
+        let set: ItemSet<4> = ['\u{100}', '\u{1a0}', '\u{1ff}']
+            .into_iter()
+            .map(|c| alphabet.item(c).unwrap())
+            .collect();
+        assert_eq!(set.len(), 3);
+        assert!(ItemSet::<4>::CAPACITY >= alphabet.len());
+        let priorities: Vec<_> = set.iter().map(|item| alphabet.priority(item)).collect();
+        assert_eq!(priorities, [1, 161, 256]);
+        assert_eq!(format!("{set:?}"), "{Item(0), Item(160), Item(255)}");
+    }
+
+    #[test]
+    fn report_problems() {
+        let alphabet = Alphabet::standard();
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+                     jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+                     PmmdzqPrVvPwwTWBwg\n\
+                     abc\n\
+                     ab\n\
+                     ab\n\
+                     ab\n\
+                     a1\n";
+        let report = Report::<1>::new(&alphabet, input, Grouping::default());
+        let item = |c| Item::from_char(c).unwrap();
+
+        assert_eq!(report.rucksacks.len(), 8);
+        assert_eq!(
+            report.rucksacks[0].as_ref().unwrap().mispacked().first(),
+            Some(item('p'))
+        );
+        assert_eq!(report.rucksacks[3], Err(RucksackError::OddLength(3)));
+        assert_eq!(
+            report.rucksacks[7],
+            Err(RucksackError::UnknownItem {
+                symbol: '1',
+                column: 2
+            }),
+        );
+
+        let badges: Vec<_> = report.groups.iter().map(|g| g.badge.clone()).collect();
+        assert_eq!(
+            badges,
+            [
+                Ok(item('r')),
+                Err(GroupError::Malformed),
+                Err(GroupError::Incomplete {
+                    found: 2,
+                    expected: 3
+                }),
+            ],
+        );
+        assert_eq!(report.groups[2].lines, 7..=8);
+        assert_eq!(report.mispacked_sum(&alphabet), 16 + 38 + 42);
+        assert_eq!(report.badges_sum(&alphabet), 18);
+        assert_eq!(report.problems(), 4);
+
+        let report = Report::<1>::new(&alphabet, "ab\nab\nab\nab\nac\nad\n", Grouping::default());
+        let both: ItemSet = [item('a'), item('b')].into_iter().collect();
+        assert_eq!(report.groups[0].badge, Err(GroupError::SeveralBadges(both)));
+        let report = Report::<1>::new(&alphabet, "ab\ncd\nef\n", Grouping::default());
+        assert_eq!(report.groups[0].badge, Err(GroupError::NoBadge));
+    }
+
+    #[test]
+    fn custom_grouping() {
+        let alphabet = Alphabet::standard();
+        let item = |c| Item::from_char(c).unwrap();
+        let input = "aa\nab\nbc\ncd\n";
+
+        let pairs = Report::<1>::new(&alphabet, input, Grouping::new(2));
+        let badges: Vec<_> = pairs.groups.iter().map(|g| g.badge.clone()).collect();
+        assert_eq!(badges, [Ok(item('a')), Ok(item('c'))]);
+
+        let sliding = Report::<1>::new(&alphabet, input, Grouping::new(2).sliding());
+        let lines: Vec<_> = sliding.groups.iter().map(|g| g.lines.clone()).collect();
+        assert_eq!(lines, [1..=2, 2..=3, 3..=4]);
+        assert_eq!(sliding.badges_sum(&alphabet), 1 + 2 + 3);
+
+        let any = Report::<1>::new(&alphabet, input, Grouping::new(4).at_least(1));
+        let all: ItemSet = "abcd".chars().map(item).collect();
+        assert_eq!(any.groups[0].badge, Err(GroupError::SeveralBadges(all)));
+
+        let short = Report::<1>::new(&alphabet, input, Grouping::new(5).sliding());
+        assert_eq!(short.groups.len(), 1);
+        assert_eq!(short.problems(), 1);
+    }
+
+    #[test]
+    fn items_in_at_least() {
+        let sets: Vec<ItemSet> = ["abc", "bcd", "cde", "a"]
+            .into_iter()
+            .map(|s| s.chars().map(|c| Item::from_char(c).unwrap()).collect())
+            .collect();
+        let chars = |set: ItemSet| -> String { set.iter().map(Item::to_char).collect() };
+        assert_eq!(chars(ItemSet::in_at_least(&sets, 1)), "abcde");
+        assert_eq!(chars(ItemSet::in_at_least(&sets, 2)), "abcd");
+        assert_eq!(chars(ItemSet::in_at_least(&sets, 3)), "c");
+        assert_eq!(chars(ItemSet::in_at_least(&sets, 4)), "");
+    }
 }
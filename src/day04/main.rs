@@ -1,46 +1,54 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct IdRange {
-    min: u32,
-    max: u32,
-}
+use std::env;
 
-impl IdRange {
-    fn parse(text: &str) -> Self {
-        let (min, max) = text.split_once('-').unwrap();
-        let min = min.parse().unwrap();
-        let max = max.parse().unwrap();
-        assert!(
-            min <= max,
-            "minimum ({min}) was greater than maximum ({max})"
-        );
-        Self { min, max }
-    }
+use advent_2022::{Interval, IntervalTree};
 
-    fn contains(self, other: IdRange) -> bool {
-        self.min <= other.min && self.max >= other.max
-    }
+type IdRange = Interval<u32>;
 
-    fn overlaps(self, other: IdRange) -> bool {
-        self.min <= other.max && self.max >= other.min
-    }
+fn parse_range(text: &str) -> IdRange {
+    let (min, max) = text.split_once('-').unwrap();
+    let min = min.parse().unwrap();
+    let max = max.parse().unwrap();
+    IdRange::try_new(min, max)
+        .unwrap_or_else(|| panic!("minimum ({min}) was greater than maximum ({max})"))
 }
 
 fn main() {
     let mut fully_contains_count = 0;
     let mut overlaps_count = 0;
+    let mut all_elves = vec![];
 
     for line in include_str!("input.txt").lines() {
         let (a, b) = line.split_once(',').unwrap();
-        let a = IdRange::parse(a);
-        let b = IdRange::parse(b);
-        if a.contains(b) || b.contains(a) {
+        let a = parse_range(a);
+        let b = parse_range(b);
+        if a.contains_interval(&b) || b.contains_interval(&a) {
             fully_contains_count += 1;
         }
-        if a.overlaps(b) {
+        if a.overlaps(&b) {
             overlaps_count += 1;
         }
+        all_elves.extend([a, b]);
     }
 
     println!("Pairs where one fully contains the other: {fully_contains_count}");
     println!("Pairs where one overlaps the other: {overlaps_count}");
+
+    // Elves are numbered from 1, in the order they appear
+    let tree: IntervalTree<u32, usize> = all_elves.into_iter().zip(1..).collect();
+    println!(
+        "Overlapping pairs among all {} elves: {}",
+        tree.len(),
+        tree.overlapping_pair_count(),
+    );
+
+    if let Some(section) = env::args().nth(1) {
+        let section = section.parse().expect("section must be a number");
+        print!("Elves assigned to section {section}:");
+        let mut elves: Vec<_> = tree.stabbing(section).iter().map(|e| e.1).collect();
+        elves.sort();
+        for elf in elves {
+            print!(" {elf}");
+        }
+        println!();
+    }
 }
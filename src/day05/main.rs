@@ -2,9 +2,11 @@
 #![feature(iter_array_chunks)]
 #![feature(get_many_mut)]
 
+use std::env;
 use std::fmt::{self, Write};
 
-use advent_2022::IteratorUtils;
+use advent_2022::{ChunkSepError, IteratorUtils};
+use thiserror::Error;
 
 #[derive(Debug, Clone, Default)]
 struct Stack {
@@ -17,16 +19,57 @@ impl Stack {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+enum MoveError {
+    #[error("stack {0} is empty")]
+    EmptySource(usize),
+    #[error("there is no stack {stack}, only stacks 1 to {count}")]
+    OutOfRange { stack: usize, count: usize },
+    #[error("can't move {requested} crates from stack {stack}, which only has {available}")]
+    NotEnoughCrates {
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line} of the drawing: {error}")]
+struct DrawingError {
+    line: usize,
+    error: ChunkSepError<char>,
+}
+
 #[derive(Debug, Clone, Default)]
 struct Stacks {
     stacks: Vec<Stack>,
 }
 
 impl Stacks {
-    pub fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Self {
+    /// Lines may have their trailing spaces trimmed, so a short last cell is
+    /// read as if it were padded with spaces.
+    pub fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Self, DrawingError> {
         let mut stacks = vec![];
-        while let line = lines.next().unwrap() && !line.is_empty() {
-            for (col, [l, value, r]) in line.chars().array_chunks_sep::<3, 1>().enumerate() {
+        let mut line_number = 0;
+        while let Some(line) = lines.next()
+            && !line.is_empty()
+        {
+            line_number += 1;
+            for (col, chunk) in line.chars().try_array_chunks_sep::<3, 1>(' ').enumerate() {
+                let [l, value, r] = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(ChunkSepError::Incomplete { partial, .. }) => {
+                        let mut cell = [' '; 3];
+                        cell[..partial.len()].copy_from_slice(&partial);
+                        cell
+                    }
+                    Err(error) => {
+                        return Err(DrawingError {
+                            line: line_number,
+                            error,
+                        });
+                    }
+                };
                 if stacks.len() <= col {
                     stacks.push(Stack::default());
                 }
@@ -38,17 +81,49 @@ impl Stacks {
         for stack in &mut stacks {
             stack.crates.reverse();
         }
-        Self { stacks }
+        Ok(Self { stacks })
+    }
+
+    /// Checks that both stacks exist, and that `from` has at least `size` crates.
+    fn check_move(&self, size: usize, from: usize, to: usize) -> Result<(), MoveError> {
+        for stack in [from, to] {
+            if stack == 0 || stack > self.stacks.len() {
+                return Err(MoveError::OutOfRange {
+                    stack,
+                    count: self.stacks.len(),
+                });
+            }
+        }
+        let available = self.stacks[from - 1].crates.len();
+        if available == 0 && size > 0 {
+            Err(MoveError::EmptySource(from))
+        } else if available < size {
+            Err(MoveError::NotEnoughCrates {
+                stack: from,
+                requested: size,
+                available,
+            })
+        } else {
+            Ok(())
+        }
     }
 
-    pub fn move_from_to(&mut self, from: usize, to: usize) {
+    pub fn move_from_to(&mut self, from: usize, to: usize) -> Result<(), MoveError> {
+        self.check_move(1, from, to)?;
         if from != to {
             let value = self.stacks[from - 1].crates.pop().unwrap();
             self.stacks[to - 1].crates.push(value);
         }
+        Ok(())
     }
 
-    pub fn move_group_from_to(&mut self, size: usize, from: usize, to: usize) {
+    pub fn move_group_from_to(
+        &mut self,
+        size: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), MoveError> {
+        self.check_move(size, from, to)?;
         if from != to {
             let [from, to] = self.stacks.get_many_mut([from - 1, to - 1]).unwrap();
             let keep_from = from.crates.len() - size;
@@ -56,6 +131,7 @@ impl Stacks {
             to.crates.extend_from_slice(values);
             from.crates.truncate(keep_from);
         }
+        Ok(())
     }
 
     pub fn stacks(&self) -> &[Stack] {
@@ -67,6 +143,39 @@ impl Stacks {
     }
 }
 
+/// Draws the stacks the same way as the puzzle input, with a row of stack
+/// numbers underneath, so parsing a drawing and displaying it gives back the
+/// same text.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self
+            .stacks
+            .iter()
+            .map(|s| s.crates.len())
+            .max()
+            .unwrap_or(0);
+        for level in (0..height).rev() {
+            for (i, stack) in self.stacks.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(' ')?;
+                }
+                match stack.crates.get(level) {
+                    Some(value) => write!(f, "[{value}]")?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        for number in 1..=self.stacks.len() {
+            if number > 1 {
+                f.write_char(' ')?;
+            }
+            write!(f, "{number:^3}")?;
+        }
+        writeln!(f)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct StackTops<'a>(&'a Stacks);
 
@@ -79,26 +188,246 @@ impl fmt::Display for StackTops<'_> {
     }
 }
 
+trait Crane {
+    /// The model name, e.g. `CrateMover 9000`.
+    fn name(&self) -> &'static str;
+
+    /// Moves `num_crates` crates from one stack to another, leaving the stacks
+    /// untouched if the move isn't possible.
+    fn move_crates(
+        &self,
+        stacks: &mut Stacks,
+        num_crates: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), MoveError>;
+}
+
+/// Moves one crate at a time, so a group of crates ends up reversed.
+struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> &'static str {
+        "CrateMover 9000"
+    }
+
+    fn move_crates(
+        &self,
+        stacks: &mut Stacks,
+        num_crates: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), MoveError> {
+        // Check the whole move first, so a bad one doesn't leave it half done
+        stacks.check_move(num_crates, from, to)?;
+        (0..num_crates).try_for_each(|_| stacks.move_from_to(from, to))
+    }
+}
+
+/// Moves a whole group of crates at once, keeping their order.
+struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> &'static str {
+        "CrateMover 9001"
+    }
+
+    fn move_crates(
+        &self,
+        stacks: &mut Stacks,
+        num_crates: usize,
+        from: usize,
+        to: usize,
+    ) -> Result<(), MoveError> {
+        stacks.move_group_from_to(num_crates, from, to)
+    }
+}
+
+const CRANES: &[&dyn Crane] = &[&CrateMover9000, &CrateMover9001];
+
+/// Finds a crane by its full name, or just its model number.
+fn crane_by_name(name: &str) -> Option<&'static dyn Crane> {
+    CRANES.iter().copied().find(|crane| {
+        crane.name() == name || crane.name().strip_prefix("CrateMover ") == Some(name)
+    })
+}
+
+/// Parses a `move N from A to B` line.
+fn parse_move(line: &str) -> Option<(usize, usize, usize)> {
+    match line.split(' ').collect::<Vec<_>>()[..] {
+        ["move", num_crates, "from", from, "to", to] => Some((
+            num_crates.parse().ok()?,
+            from.parse().ok()?,
+            to.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
+
 fn main() {
-    let mut lines = include_str!("input.txt").lines();
-    let mut stacks_p1 = Stacks::parse(&mut lines);
-    let mut stacks_p2 = stacks_p1.clone();
-
-    for line in lines {
-        let mut line = line.split(' ');
-        assert_eq!(line.next(), Some("move"));
-        let num_crates = line.next().unwrap().parse().unwrap();
-        assert_eq!(line.next(), Some("from"));
-        let from = line.next().unwrap().parse().unwrap();
-        assert_eq!(line.next(), Some("to"));
-        let to = line.next().unwrap().parse().unwrap();
-
-        for _ in 0..num_crates {
-            stacks_p1.move_from_to(from, to);
+    let mut show_after = None;
+    let mut cranes = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Draw the stacks after this many moves
+            "--show" => {
+                let moves = args.next().and_then(|n| n.parse().ok());
+                show_after = Some(moves.expect("--show must be followed by a number of moves"));
+            }
+            name => cranes.push(crane_by_name(name).unwrap_or_else(|| {
+                let names: Vec<_> = CRANES.iter().map(|crane| crane.name()).collect();
+                panic!("unknown crane {name:?}, expected one of {names:?}")
+            })),
+        }
+    }
+    if cranes.is_empty() {
+        cranes.extend(CRANES);
+    }
+
+    let input = include_str!("input.txt");
+    let mut lines = input.lines();
+    let stacks = match Stacks::parse(&mut lines) {
+        Ok(stacks) => stacks,
+        Err(err) => {
+            println!("{err}");
+            return;
+        }
+    };
+    let first_move_line = input.lines().count() - lines.clone().count() + 1;
+    // Every crane works on its own copy of the stacks
+    let mut runs: Vec<_> = cranes
+        .into_iter()
+        .map(|crane| (crane, stacks.clone()))
+        .collect();
+
+    for ((moves, line_number), line) in (1..).zip(first_move_line..).zip(lines) {
+        let Some((num_crates, from, to)) = parse_move(line) else {
+            println!("Line {line_number}: expected `move N from A to B`, found {line:?}");
+            continue;
+        };
+
+        for (crane, stacks) in &mut runs {
+            if let Err(err) = crane.move_crates(stacks, num_crates, from, to) {
+                println!("Line {line_number}, {}: {err}", crane.name());
+            }
+            if show_after == Some(moves) {
+                println!("After {moves} moves, {}:\n{stacks}", crane.name());
+            }
         }
-        stacks_p2.move_group_from_to(num_crates, from, to);
     }
 
-    println!("Top of stacks P1: [{}]", stacks_p1.tops());
-    println!("Top of stacks P2: [{}]", stacks_p2.tops());
+    for (crane, stacks) in &runs {
+        println!("Top of stacks, {}: [{}]", crane.name(), stacks.tops());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drawing_round_trip() {
+        let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+        let mut stacks = Stacks::parse(&mut drawing.lines()).unwrap();
+        assert_eq!(stacks.to_string(), drawing);
+
+        let mut lines = drawing.lines().chain(["", "move 1 from 2 to 1"]);
+        assert_eq!(Stacks::parse(&mut lines).unwrap().to_string(), drawing);
+        assert_eq!(lines.next(), Some("move 1 from 2 to 1"));
+
+        stacks.move_group_from_to(2, 1, 3).unwrap();
+        let after = "    [D] [N]\n    [C] [Z]\n    [M] [P]\n 1   2   3 \n";
+        assert_eq!(stacks.to_string(), after);
+        assert_eq!(
+            Stacks::parse(&mut after.lines()).unwrap().to_string(),
+            after
+        );
+    }
+
+    #[test]
+    fn trimmed_drawing() {
+        let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+        let trimmed: String = drawing
+            .lines()
+            .map(|line| line.trim_end().to_owned() + "\n")
+            .collect();
+        let stacks = Stacks::parse(&mut trimmed.lines()).unwrap();
+        assert_eq!(stacks.to_string(), drawing);
+
+        assert_eq!(
+            Stacks::parse(&mut "[A] [B]\n[C]-[D]\n 1   2".lines()).unwrap_err(),
+            DrawingError {
+                line: 2,
+                error: ChunkSepError::BadSeparator {
+                    index: 3,
+                    found: '-'
+                },
+            },
+        );
+    }
+
+    #[test]
+    fn bad_moves() {
+        let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+        let mut stacks = Stacks::parse(&mut drawing.lines()).unwrap();
+        assert_eq!(
+            stacks.move_from_to(0, 1),
+            Err(MoveError::OutOfRange { stack: 0, count: 3 }),
+        );
+        assert_eq!(
+            stacks.move_group_from_to(1, 1, 4),
+            Err(MoveError::OutOfRange { stack: 4, count: 3 }),
+        );
+        assert_eq!(
+            stacks.move_group_from_to(4, 2, 1),
+            Err(MoveError::NotEnoughCrates {
+                stack: 2,
+                requested: 4,
+                available: 3
+            }),
+        );
+        assert_eq!(stacks.to_string(), drawing);
+
+        stacks.move_from_to(3, 1).unwrap();
+        assert_eq!(stacks.move_from_to(3, 2), Err(MoveError::EmptySource(3)));
+        assert_eq!(stacks.move_group_from_to(0, 3, 2), Ok(()));
+        assert_eq!(stacks.tops().to_string(), "PD ");
+
+        assert_eq!(parse_move("move 1 from 2 to 1"), Some((1, 2, 1)));
+        assert_eq!(parse_move("move 1 from 2 to"), None);
+        assert_eq!(parse_move("move one from 2 to 1"), None);
+    }
+
+    #[test]
+    fn cranes() {
+        let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+        let moves = [(1, 2, 1), (3, 1, 3), (2, 2, 1), (1, 1, 2)];
+        let tops = |crane: &dyn Crane| {
+            let mut stacks = Stacks::parse(&mut drawing.lines()).unwrap();
+            for (num_crates, from, to) in moves {
+                crane
+                    .move_crates(&mut stacks, num_crates, from, to)
+                    .unwrap();
+            }
+            stacks.tops().to_string()
+        };
+        assert_eq!(tops(crane_by_name("9000").unwrap()), "CMZ");
+        assert_eq!(tops(crane_by_name("CrateMover 9001").unwrap()), "MCD");
+        assert!(crane_by_name("9002").is_none());
+
+        // A failed move leaves the stacks as they were, whichever crane makes it
+        for &crane in CRANES {
+            let mut stacks = Stacks::parse(&mut drawing.lines()).unwrap();
+            assert_eq!(
+                crane.move_crates(&mut stacks, 3, 1, 3),
+                Err(MoveError::NotEnoughCrates {
+                    stack: 1,
+                    requested: 3,
+                    available: 2
+                }),
+            );
+            assert_eq!(stacks.to_string(), drawing);
+        }
+    }
 }
@@ -1,31 +1,126 @@
-use std::time::Instant;
-
-fn all_unique(buffer: &[char]) -> bool {
-    let mut flags = 0u32;
-    for &c in buffer {
-        assert!(c.is_ascii_lowercase());
-        let flag = 1 << (c as u32 - 'a' as u32);
-        if flags & flag != 0 {
-            return false;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::time::{Duration, Instant};
+
+use advent_2022::{distinct_byte_windows, DistinctTracker};
+
+fn message_start_index<const N: usize>(signal: &str) -> usize {
+    distinct_byte_windows(signal.bytes(), N).next().unwrap()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarkerKind {
+    Packet,
+    Message,
+}
+
+impl Display for MarkerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Packet => "packet",
+            Self::Message => "message",
+        })
+    }
+}
+
+/// Finds the first run of `N` distinct bytes in a stream fed one byte at a time.
+#[derive(Debug, Clone)]
+struct MarkerFinder<const N: usize> {
+    tracker: DistinctTracker<u8, [usize; 256]>,
+    found: Option<u64>,
+}
+
+impl<const N: usize> MarkerFinder<N> {
+    pub fn new() -> Self {
+        Self {
+            tracker: DistinctTracker::bytes(N),
+            found: None,
+        }
+    }
+
+    pub fn found(&self) -> Option<u64> {
+        self.found
+    }
+
+    /// Returns the marker's offset if `byte`, at offset `pos`, completes it.
+    pub fn push(&mut self, byte: u8, pos: u64) -> Option<u64> {
+        if self.found.is_none() && self.tracker.push(byte) {
+            self.found = Some(pos + 1);
+            self.found
+        } else {
+            None
         }
-        flags |= flag;
     }
-    true
 }
 
-fn message_start_index<const N: usize>(signal: &str) -> usize {
-    let mut signal = signal.chars();
-    let mut buffer: [char; N] = std::array::from_fn(|_| signal.next().unwrap());
-    let mut pos = N;
-    while !all_unique(&buffer) {
-        let c = signal.next().unwrap();
-        buffer[pos % N] = c;
-        pos += 1;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StreamStats {
+    bytes_read: u64,
+    elapsed: Duration,
+}
+
+impl StreamStats {
+    /// Throughput in MiB per second.
+    pub fn throughput(&self) -> f64 {
+        self.bytes_read as f64 / (1024.0 * 1024.0) / self.elapsed.as_secs_f64()
     }
-    pos
+}
+
+/// Scan `reader` in buffered chunks, reporting markers of `P` and `M` distinct bytes as they
+/// are found. Stops reading once both have been found.
+fn decode_stream<const P: usize, const M: usize>(
+    mut reader: impl Read,
+    mut on_marker: impl FnMut(MarkerKind, u64),
+) -> io::Result<StreamStats> {
+    let start = Instant::now();
+    let mut packet = MarkerFinder::<P>::new();
+    let mut message = MarkerFinder::<M>::new();
+    let mut buffer = vec![0; 64 * 1024];
+    let mut bytes_read = 0u64;
+
+    while packet.found().is_none() || message.found().is_none() {
+        let len = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        for (i, &byte) in buffer[..len].iter().enumerate() {
+            let pos = bytes_read + i as u64;
+            if let Some(offset) = packet.push(byte, pos) {
+                on_marker(MarkerKind::Packet, offset);
+            }
+            if let Some(offset) = message.push(byte, pos) {
+                on_marker(MarkerKind::Message, offset);
+            }
+        }
+        bytes_read += len as u64;
+    }
+
+    Ok(StreamStats {
+        bytes_read,
+        elapsed: start.elapsed(),
+    })
 }
 
 fn main() {
+    if let Some(path) = env::args_os().nth(1) {
+        let file = File::open(path).unwrap();
+        let stats = decode_stream::<4, 14>(file, |kind, offset| {
+            println!("Start of {kind} at index {offset}");
+        })
+        .unwrap();
+        println!(
+            "Read {} bytes in {:?} ({:.1} MiB/s)",
+            stats.bytes_read,
+            stats.elapsed,
+            stats.throughput(),
+        );
+        return;
+    }
+
     let signal = include_str!("input.txt").trim();
     let time_a = Instant::now();
     let packet_start = message_start_index::<4>(signal);
@@ -37,3 +132,44 @@ fn main() {
     println!("Start of message at index {message_start}");
     println!("  Time: {:?}", time_c - time_b);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out at most one byte per read, to exercise chunk boundaries.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(1);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn stream_markers() {
+        for signal in [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "nppdvjthqldpwncqszvftbrmjlhg",
+            // Multi-byte characters, where byte and char offsets differ
+            "ééαβγδabcdefghijklmnop",
+        ] {
+            let mut markers = vec![];
+            let stats = decode_stream::<4, 14>(Trickle(signal.as_bytes()), |kind, offset| {
+                markers.push((kind, offset as usize));
+            })
+            .unwrap();
+            assert_eq!(
+                markers,
+                [
+                    (MarkerKind::Packet, message_start_index::<4>(signal)),
+                    (MarkerKind::Message, message_start_index::<14>(signal)),
+                ],
+            );
+            assert_eq!(stats.bytes_read as usize, markers[1].1);
+        }
+    }
+}
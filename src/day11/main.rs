@@ -3,7 +3,7 @@
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use advent_2022::IteratorUtils;
+use advent_2022::{IteratorUtils, StrUtils};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -239,10 +239,10 @@ fn run_monkeys(mut monkeys: Vec<Monkey>, wrap_at: u64, is_p1: bool) {
 
 fn main() {
     let monkeys: Vec<_> = include_str!("input.txt")
-        .split("\n\n")
+        .paragraphs()
         .enumerate()
         .map(|(i, monkey_def)| {
-            let monkey: Monkey = monkey_def.parse().unwrap();
+            let monkey: Monkey = monkey_def.text.parse().unwrap();
             assert_eq!(monkey.id, i);
             monkey
         })
@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
+use advent_2022::StrUtils;
 use thiserror::Error;
 
 #[derive(Debug, Clone, Eq)]
@@ -119,22 +120,19 @@ fn main() {
     let mut all_packets = vec![divider_a.clone(), divider_b.clone()];
     let mut right_order_pairs = 0usize;
 
-    let mut lines = include_str!("input.txt").lines();
-    for pair_num in 1.. {
-        let a = lines.next().unwrap().parse().unwrap();
-        let b = lines.next().unwrap().parse().unwrap();
+    for (pair_num, pair) in (1..).zip(include_str!("input.txt").paragraphs()) {
+        let mut packets = pair.lines();
+        let (Some(a), Some(b), None) = (packets.next(), packets.next(), packets.next()) else {
+            panic!("Expected a pair of packets at line {}", pair.start_line);
+        };
+        let a: Packet = a.parse().unwrap();
+        let b: Packet = b.parse().unwrap();
 
         if a <= b {
             right_order_pairs += pair_num;
         }
         all_packets.push(a);
         all_packets.push(b);
-
-        match lines.next() {
-            None => break,
-            Some("") => continue,
-            Some(other) => panic!("Expected blank line, not {other:?}"),
-        }
     }
 
     println!("Correctly ordered pairs: {right_order_pairs}");
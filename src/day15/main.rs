@@ -1,147 +1,42 @@
-use advent_2022::{simple_parse, Vec2};
-use std::{cmp::Ordering, collections::HashSet};
+pub mod render;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub struct InclRange {
-    min: i32,
-    max: i32,
-}
-
-impl InclRange {
-    pub const fn new(min: i32, max: i32) -> Self {
-        assert!(min <= max, "min was greater than max");
-        Self { min, max }
-    }
-
-    pub const fn try_new(min: i32, max: i32) -> Option<Self> {
-        if min <= max {
-            Some(Self { min, max })
-        } else {
-            None
-        }
-    }
-
-    pub const fn from_center_radius(center: i32, radius: u32) -> Self {
-        Self {
-            min: center - radius as i32,
-            max: center + radius as i32,
-        }
-    }
-
-    pub const fn from_point(point: i32) -> Self {
-        Self {
-            min: point,
-            max: point,
-        }
-    }
-
-    pub const fn size(&self) -> u32 {
-        (self.max - self.min) as u32 + 1
-    }
-
-    pub const fn extend_min(&self, diff: u32) -> Self {
-        Self {
-            min: self.min - diff as i32,
-            max: self.max,
-        }
-    }
+use advent_2022::{simple_parse, Interval, RangeSet, Vec2};
+use render::Heatmap;
+use std::collections::HashSet;
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::thread;
 
-    pub const fn extend_max(&self, diff: u32) -> Self {
-        Self {
-            min: self.min,
-            max: self.max + diff as i32,
-        }
-    }
-
-    pub const fn contains(&self, point: i32) -> bool {
-        self.min <= point && point <= self.max
-    }
+pub type InclRange = Interval<i32>;
 
-    pub const fn compare_point(&self, point: i32) -> Ordering {
-        if point < self.min {
-            Ordering::Greater
-        } else if point > self.max {
-            Ordering::Less
-        } else {
-            Ordering::Equal
-        }
-    }
-}
-
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-pub struct RangeSet {
-    ranges: Vec<InclRange>,
-    coverage: u32,
+/// An inclusive, axis-aligned rectangle of cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: InclRange,
+    pub y: InclRange,
 }
 
-impl RangeSet {
-    pub fn coverage(&self) -> u32 {
-        self.coverage
-    }
-
-    pub fn ranges(&self) -> &[InclRange] {
-        self.ranges.as_slice()
+impl Rect {
+    pub const fn square(range: InclRange) -> Self {
+        Self { x: range, y: range }
     }
 
-    pub fn add(&mut self, ranges: InclRange) {
-        match self.find_intersection(ranges) {
-            Ok((min_index, max_index)) => {
-                let dropped_max = self
-                    .ranges
-                    .drain(min_index + 1..=max_index)
-                    .inspect(|r| self.coverage -= r.size())
-                    .last()
-                    .map(|r| r.max);
-                let updated = &mut self.ranges[min_index];
-
-                self.coverage -= updated.size();
-                if ranges.min < updated.min {
-                    updated.min = ranges.min;
-                }
-                if let Some(dropped_max) = dropped_max {
-                    updated.max = dropped_max.max(ranges.max);
-                } else if ranges.max > updated.max {
-                    updated.max = ranges.max;
-                }
-                self.coverage += updated.size();
-            }
-            Err(index) => {
-                self.ranges.insert(index, ranges);
-                self.coverage += ranges.size();
-            }
-        }
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.x.contains(point.x) && self.y.contains(point.y)
     }
+}
 
-    pub fn contains_point(&self, point: i32) -> bool {
-        self.ranges
-            .binary_search_by(|r| r.compare_point(point))
-            .is_ok()
-    }
-
-    fn find_intersection(&self, ranges: InclRange) -> Result<(usize, usize), usize> {
-        let min_index = self
-            .ranges
-            .binary_search_by(|r| r.extend_max(1).compare_point(ranges.min));
-        let max_index = self
-            .ranges
-            .binary_search_by(|r| r.extend_min(1).compare_point(ranges.max));
-        match (min_index, max_index) {
-            (Ok(a), Ok(b)) => Ok((a, b)),
-            (Ok(a), Err(b)) => Ok((a, b - 1)),
-            (Err(a), Ok(b)) => Ok((a, b)),
-            (Err(a), Err(b)) => {
-                if a == b {
-                    Err(a)
-                } else {
-                    Ok((a, b - 1))
-                }
-            }
-        }
-    }
+/// A diagonal line of cells, either `x + y = u` or `x - y = v`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagonal {
+    U(i64),
+    V(i64),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Region {
+pub struct Region {
     pub pos: Vec2,
     pub radius: u32,
 }
@@ -151,6 +46,38 @@ impl Region {
         self.pos.manhattan_dist(point) <= self.radius
     }
 
+    /// The lines `x + y = u` along the diamond's two edges with slope -1.
+    pub fn u_edges(&self) -> [i64; 2] {
+        let u = self.pos.x as i64 + self.pos.y as i64;
+        [u - self.radius as i64, u + self.radius as i64]
+    }
+
+    /// The lines `x - y = v` along the diamond's two edges with slope 1.
+    pub fn v_edges(&self) -> [i64; 2] {
+        let v = self.pos.x as i64 - self.pos.y as i64;
+        [v - self.radius as i64, v + self.radius as i64]
+    }
+
+    /// The x values of the cells this region covers along `line`.
+    pub fn xs_on(&self, line: Diagonal) -> Option<Interval<i64>> {
+        let (x, y) = (self.pos.x as i64, self.pos.y as i64);
+        // Along the line, y - self.y = c - dx for a `U` line, or c + dx for a `V` line,
+        // where dx = x - self.x. The distance is then smallest, at |c|, for dx between
+        // 0 and c (or -c), and grows by 2 for every step past either end.
+        let (c, end) = match line {
+            Diagonal::U(u) => (u - x - y, u - x - y),
+            Diagonal::V(v) => (x - v - y, v + y - x),
+        };
+        let slack = self.radius as i64 - c.abs();
+        if slack < 0 {
+            return None;
+        }
+        Some(Interval::new(
+            x + end.min(0) - slack / 2,
+            x + end.max(0) + slack / 2,
+        ))
+    }
+
     pub fn border(&self) -> RegionBorder {
         RegionBorder {
             center: self.pos,
@@ -161,7 +88,7 @@ impl Region {
 }
 
 #[derive(Debug, Clone)]
-struct RegionBorder {
+pub struct RegionBorder {
     center: Vec2,
     next_pos: Vec2,
     side: u8,
@@ -193,16 +120,12 @@ impl Iterator for RegionBorder {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
-struct Scan {
+pub struct Scan {
     sensors: Vec<Region>,
     beacons: HashSet<Vec2>,
 }
 
 impl Scan {
-    pub fn sensors(&self) -> &[Region] {
-        &self.sensors
-    }
-
     pub fn is_covered(&self, point: Vec2) -> bool {
         self.sensors.iter().any(|s| s.contains(point))
     }
@@ -215,20 +138,83 @@ impl Scan {
         self.beacons.insert(beacon);
     }
 
-    pub fn ranges_on_row(&self, row: i32) -> RangeSet {
+    pub fn ranges_on_row(&self, row: i32) -> RangeSet<i32> {
         let mut ranges = RangeSet::default();
+        self.ranges_on_row_into(row, &mut ranges);
+        ranges
+    }
+
+    /// Like [`ranges_on_row`](Self::ranges_on_row), but reuses an existing set's storage.
+    pub fn ranges_on_row_into(&self, row: i32, ranges: &mut RangeSet<i32>) {
+        ranges.clear();
         for sensor in &self.sensors {
             let dist_to_row = sensor.pos.y.abs_diff(row);
             if let Some(r) = sensor.radius.checked_sub(dist_to_row) {
-                let range = InclRange::from_center_radius(sensor.pos.x, r);
+                let range = InclRange::from_center_radius(sensor.pos.x, r as i32);
                 ranges.add(range);
             }
         }
-        ranges
     }
 
-    pub fn impossible_on_row(&self, row: i32) -> u32 {
-        let mut result = self.ranges_on_row(row).coverage();
+    /// Call `f` with the ranges on each row, split across `threads` worker threads, and
+    /// return the result for the first row where it returns `Some`.
+    ///
+    /// Each thread works through its own contiguous block of rows with a single reused
+    /// [`RangeSet`]. Threads share the earliest matching row found so far, and stop once
+    /// they pass it, so the result doesn't depend on how the threads are scheduled.
+    pub fn par_scan_rows<R, F>(&self, rows: InclRange, threads: usize, f: F) -> Option<R>
+    where
+        R: Send,
+        F: Fn(i32, &RangeSet<i32>) -> Option<R> + Sync,
+    {
+        // An i32 range has at most 2^32 rows, so this can't overflow
+        let len = rows.max() as i64 - rows.min() as i64 + 1;
+        let threads = (threads as u64).clamp(1, len as u64) as i64;
+        let first_match = AtomicI64::new(i64::MAX);
+        let (first_match, f) = (&first_match, &f);
+        // Blocks of equal size, except the first `len % threads` get one extra row
+        let block_start = |i: i64| rows.min() as i64 + i * (len / threads) + i.min(len % threads);
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|i| {
+                    let start = block_start(i) as i32;
+                    let end = (block_start(i + 1) - 1) as i32;
+                    scope.spawn(move || {
+                        let mut ranges = RangeSet::default();
+                        for row in start..=end {
+                            if row as i64 > first_match.load(Ordering::Relaxed) {
+                                break;
+                            }
+                            self.ranges_on_row_into(row, &mut ranges);
+                            if let Some(result) = f(row, &ranges) {
+                                first_match.fetch_min(row as i64, Ordering::Relaxed);
+                                return Some(result);
+                            }
+                        }
+                        None
+                    })
+                })
+                .collect();
+            // A block only stops early after an earlier match, so the first block with a
+            // result has the first matching row.
+            workers
+                .into_iter()
+                .find_map(|worker| worker.join().unwrap())
+        })
+    }
+
+    /// Find an uncovered point within `bounds`, checking rows in parallel.
+    pub fn par_find_uncovered(&self, bounds: Rect, threads: usize) -> Option<Vec2> {
+        self.par_scan_rows(bounds.y, threads, |y, ranges| {
+            let x = ranges.first_uncovered(bounds.x)?;
+            Some(Vec2::new(x, y))
+        })
+    }
+
+    pub fn impossible_on_row(&self, row: i32) -> u64 {
+        // A row of i32 cells always has a u64 coverage
+        let mut result = self.ranges_on_row(row).coverage() as u64;
         for beacon in &self.beacons {
             if beacon.y == row {
                 result -= 1;
@@ -236,14 +222,172 @@ impl Scan {
         }
         result
     }
-}
 
-fn main() {
-    const ROW: i32 = 2_000_000;
-    const BEACON_RANGE: InclRange = InclRange::new(0, ROW * 2);
+    /// The smallest rectangle containing every sensor's region.
+    pub fn bounds(&self) -> Option<Rect> {
+        let x = self
+            .sensors
+            .iter()
+            .map(|s| InclRange::from_center_radius(s.pos.x, s.radius as i32))
+            .reduce(|a, b| a.hull(&b))?;
+        let y = self
+            .sensors
+            .iter()
+            .map(|s| InclRange::from_center_radius(s.pos.y, s.radius as i32))
+            .reduce(|a, b| a.hull(&b))?;
+        Some(Rect { x, y })
+    }
+
+    /// The number of covered cells in the whole plane.
+    pub fn total_coverage(&self) -> u64 {
+        self.bounds().map_or(0, |rect| self.coverage_in(rect))
+    }
 
+    /// The number of covered cells within `rect`.
+    pub fn coverage_in(&self, rect: Rect) -> u64 {
+        let mut total = 0;
+        self.sweep_rows(rect, |rows, first, last| {
+            // Coverage is linear between critical rows, so sum it as an arithmetic series.
+            total += (first + last) * rows.len() / 2;
+        });
+        total
+    }
+
+    /// Every uncovered cell within `rect`, in row-major order.
+    pub fn uncovered_in(&self, rect: Rect) -> Vec<Vec2> {
+        let full = rect.x.len();
+        let mut cells = vec![];
+        self.sweep_rows(rect, |rows, first, last| {
+            if first == full && last == full {
+                return;
+            }
+            for y in rows {
+                let gaps = self.ranges_on_row(y).complement(rect.x);
+                cells.extend(
+                    gaps.ranges()
+                        .iter()
+                        .flat_map(|r| r.iter())
+                        .map(|x| Vec2::new(x, y)),
+                );
+            }
+        });
+        cells
+    }
+
+    fn row_coverage_in(&self, row: i32, xs: InclRange) -> u64 {
+        let bounds = RangeSet::from_iter([xs]);
+        self.ranges_on_row(row).intersection(&bounds).coverage() as u64
+    }
+
+    /// Split the rows of `rect` into runs where the covered cells per row change linearly,
+    /// and call `f` with each run and the coverage of its first and last rows.
+    ///
+    /// In `(x + y, x - y)` coordinates every diamond edge is an axis-aligned line, so the
+    /// order of the region edges on a row can only change where two of those lines cross,
+    /// or where one crosses a side of `rect`.
+    fn sweep_rows(&self, rect: Rect, mut f: impl FnMut(InclRange, u64, u64)) {
+        let u_lines: Vec<i64> = self.sensors.iter().flat_map(|s| s.u_edges()).collect();
+        let v_lines: Vec<i64> = self.sensors.iter().flat_map(|s| s.v_edges()).collect();
+        let (x0, x1) = (rect.x.min() as i64, rect.x.max() as i64);
+
+        let mut critical = vec![rect.y.min() as i64, rect.y.max() as i64];
+        for s in &self.sensors {
+            let r = s.radius as i64;
+            let y = s.pos.y as i64;
+            critical.extend([y - r, y, y + r]);
+        }
+        for &u in &u_lines {
+            critical.extend([u - x0, u - x1]);
+            for &v in &v_lines {
+                // x + y = u and x - y = v cross at y = (u - v) / 2
+                critical.extend([(u - v).div_euclid(2), (u - v + 1).div_euclid(2)]);
+            }
+        }
+        for &v in &v_lines {
+            critical.extend([x0 - v, x1 - v]);
+        }
+        let (y0, y1) = (rect.y.min() as i64, rect.y.max() as i64);
+        critical.retain(|y| (y0..=y1).contains(y));
+        critical.sort_unstable();
+        critical.dedup();
+
+        for (i, &row) in critical.iter().enumerate() {
+            let row = row as i32;
+            let coverage = self.row_coverage_in(row, rect.x);
+            f(InclRange::from_point(row), coverage, coverage);
+
+            let next = critical.get(i + 1).map_or(row, |&next| next as i32);
+            if let Some(rows) = InclRange::try_new(row + 1, next - 1) {
+                let first = self.row_coverage_in(rows.min(), rect.x);
+                let last = self.row_coverage_in(rows.max(), rect.x);
+                f(rows, first, last);
+            }
+        }
+    }
+
+    /// Find every uncovered cell within `bounds` that is next to a covered cell, along with
+    /// any uncovered corners of `bounds`.
+    ///
+    /// That includes every isolated uncovered cell, but larger uncovered areas are only
+    /// represented by the cells along their edges. Rather than checking every cell, this
+    /// works out which stretches of each line just outside a region are covered by others.
+    pub fn find_uncovered(&self, bounds: Rect) -> Vec<Vec2> {
+        let (x0, x1) = (bounds.x.min() as i64, bounds.x.max() as i64);
+        let (y0, y1) = (bounds.y.min() as i64, bounds.y.max() as i64);
+        let mut found: Vec<Vec2> = [(x0, y0), (x1, y0), (x0, y1), (x1, y1)]
+            .into_iter()
+            .map(|(x, y)| Vec2::new(x as i32, y as i32))
+            .filter(|&p| !self.is_covered(p))
+            .collect();
+
+        let mut covered = RangeSet::default();
+        for s in &self.sensors {
+            let outside = Region {
+                pos: s.pos,
+                radius: s.radius + 1,
+            };
+            let [u_min, u_max] = outside.u_edges();
+            let [v_min, v_max] = outside.v_edges();
+            let (x, r) = (s.pos.x as i64, outside.radius as i64);
+            // Each edge just outside the region, as a line and the x values of its cells
+            let edges = [
+                (Diagonal::U(u_min), x - r, x),
+                (Diagonal::U(u_max), x, x + r),
+                (Diagonal::V(v_min), x - r, x),
+                (Diagonal::V(v_max), x, x + r),
+            ];
+            for (line, min, max) in edges {
+                // Keep to the cells with both x and y within `bounds`
+                let (y_min, y_max) = match line {
+                    Diagonal::U(u) => (u - y1, u - y0),
+                    Diagonal::V(v) => (v + y0, v + y1),
+                };
+                let Some(xs) = Interval::try_new(min.max(x0).max(y_min), max.min(x1).min(y_max))
+                else {
+                    continue;
+                };
+                covered.clear();
+                covered.extend(self.sensors.iter().filter_map(|s| s.xs_on(line)));
+                for gap in covered.complement(xs).ranges() {
+                    found.extend(gap.iter().map(|x| {
+                        let y = match line {
+                            Diagonal::U(u) => u - x,
+                            Diagonal::V(v) => x - v,
+                        };
+                        Vec2::new(x as i32, y as i32)
+                    }));
+                }
+            }
+        }
+        found.sort_unstable_by_key(|p| (p.y, p.x));
+        found.dedup();
+        found
+    }
+}
+
+fn parse_scan(text: &str) -> Scan {
     let mut scan = Scan::default();
-    for line in include_str!("input.txt").lines() {
+    for line in text.lines() {
         let (sensor_x, sensor_y, beacon_x, beacon_y) = simple_parse!(
             line => "Sensor at x=", @, ", y=", @, ": closest beacon is at x=", @, ", y=", @,
         )
@@ -252,21 +396,65 @@ fn main() {
         let beacon = Vec2::new(beacon_x, beacon_y);
         scan.add_sensor_beacon(sensor, beacon);
     }
+    scan
+}
+
+fn main() {
+    const ROW: i32 = 2_000_000;
+    let beacon_range = InclRange::new(0, ROW * 2);
+
+    let scan = parse_scan(include_str!("input.txt"));
 
     println!(
         "Impossible positions at {ROW}: {}",
         scan.impossible_on_row(ROW)
     );
 
-    let beacon_pos = scan
-        .sensors()
-        .iter()
-        .flat_map(|s| s.border())
-        .filter(|&p| BEACON_RANGE.contains(p.x) && BEACON_RANGE.contains(p.y) && !scan.is_covered(p))
-        .next()
-        .unwrap();
-    let tuning_freq = beacon_pos.x as u64 * 4_000_000 + beacon_pos.y as u64;
-    println!("Beacon position: {beacon_pos}, tuning frequency: {tuning_freq}");
+    let search_area = Rect::square(beacon_range);
+    let uncovered = scan.find_uncovered(search_area);
+    match uncovered[..] {
+        [beacon_pos] => {
+            let tuning_freq = beacon_pos.x as u64 * 4_000_000 + beacon_pos.y as u64;
+            println!("Beacon position: {beacon_pos}, tuning frequency: {tuning_freq}");
+        }
+        [] => println!("No uncovered position in search area"),
+        _ => {
+            // Without a single position there's no tuning frequency to report
+            println!("Found {} uncovered positions:", uncovered.len());
+            for pos in &uncovered {
+                println!("  {pos}");
+            }
+        }
+    }
+
+    let mut heatmap = Heatmap::render(&scan, search_area, 64, 32);
+    for &pos in &uncovered {
+        heatmap.mark(pos);
+    }
+    print!("{heatmap}");
+    if let Some(path) = env::args_os().nth(1) {
+        let mut heatmap = Heatmap::render(&scan, search_area, 1024, 1024);
+        for &pos in &uncovered {
+            heatmap.mark(pos);
+        }
+        heatmap
+            .write_ppm(BufWriter::new(File::create(path).unwrap()))
+            .unwrap();
+    }
+
+    println!("Total covered cells: {}", scan.total_coverage());
+    let covered = scan.coverage_in(search_area);
+    let uncovered = search_area.x.len() * search_area.y.len() - covered;
+    println!("Covered cells in search area: {covered}");
+    // Only list uncovered cells when there are few enough to be worth reading
+    if uncovered <= 16 {
+        println!(
+            "Uncovered cells in search area: {:?}",
+            scan.uncovered_in(search_area),
+        );
+    } else {
+        println!("Uncovered cells in search area: {uncovered}");
+    }
 }
 
 #[cfg(test)]
@@ -274,18 +462,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_range_set() {
-        let mut ranges = RangeSet::default();
-        let r12 = InclRange::try_new(1, 2).unwrap();
-        let r33 = InclRange::try_new(3, 3).unwrap();
-        let r48 = InclRange::try_new(4, 8).unwrap();
-        ranges.add(r12);
-        ranges.add(r48);
-        assert_eq!(ranges.coverage(), 7);
-        assert_eq!(ranges.ranges(), [r12, r48]);
-        ranges.add(r33);
-        assert_eq!(ranges.coverage(), 8);
-        assert_eq!(ranges.ranges(), [InclRange::try_new(1, 8).unwrap()]);
+    fn test_example() {
+        let scan = parse_scan(include_str!("test.txt"));
+        assert_eq!(scan.impossible_on_row(10), 26);
+        assert_eq!(
+            scan.find_uncovered(Rect::square(InclRange::new(0, 20))),
+            [Vec2::new(14, 11)],
+        );
     }
 
     #[test]
@@ -302,4 +485,130 @@ mod tests {
         assert_eq!(b.next(), Some(Vec2::new(-1, 1)));
         assert_eq!(b.next(), None);
     }
+
+    #[test]
+    fn test_par_find_uncovered() {
+        let scan = parse_scan(include_str!("test.txt"));
+        let search = Rect::square(InclRange::new(0, 20));
+        for threads in [1, 3, 8, 100] {
+            assert_eq!(
+                scan.par_find_uncovered(search, threads),
+                Some(Vec2::new(14, 11)),
+            );
+        }
+        let covered = Rect::square(InclRange::new(10, 12));
+        assert_eq!(scan.par_find_uncovered(covered, 2), None);
+
+        // With an uncovered row in every block, the first one still wins
+        let rows = InclRange::new(-100, 100);
+        let even = |y: i32, _: &RangeSet<i32>| (y % 2 == 0).then_some(y);
+        for threads in [1, 2, 7, 64, 201] {
+            for _ in 0..20 {
+                assert_eq!(scan.par_scan_rows(rows, threads, even), Some(-100));
+            }
+        }
+        let search = Rect {
+            x: InclRange::new(-5, 30),
+            y: InclRange::new(-20, 40),
+        };
+        let first = scan.uncovered_in(search).first().copied();
+        for threads in [2, 5, 16] {
+            assert_eq!(scan.par_find_uncovered(search, threads), first);
+        }
+        // The first uncovered cell always touches a covered one or a corner
+        assert_eq!(scan.find_uncovered(search).first().copied(), first);
+
+        // Blocks reach the ends of the i32 range without overflowing
+        let last_rows = InclRange::new(i32::MAX - 5, i32::MAX);
+        for threads in [1, 2, 4, 6, usize::MAX] {
+            let last = scan.par_scan_rows(last_rows, threads, |y, _| (y == i32::MAX).then_some(y));
+            assert_eq!(last, Some(i32::MAX));
+        }
+        let all_rows = InclRange::new(i32::MIN, i32::MAX);
+        let first = scan.par_scan_rows(all_rows, 3, |y, _| Some(y));
+        assert_eq!(first, Some(i32::MIN));
+    }
+
+    #[test]
+    fn test_find_uncovered_corners() {
+        let mut scan = Scan::default();
+        scan.add_sensor_beacon(Vec2::new(5, 5), Vec2::new(5, 12));
+        // Only the four corner cells are outside the diamond
+        let bounds = Rect::square(InclRange::new(1, 9));
+        let found = scan.find_uncovered(bounds);
+        assert_eq!(
+            found,
+            [
+                Vec2::new(1, 1),
+                Vec2::new(9, 1),
+                Vec2::new(1, 9),
+                Vec2::new(9, 9),
+            ],
+        );
+        assert_eq!(scan.uncovered_in(bounds), found);
+    }
+
+    #[test]
+    fn test_find_uncovered_diagonal_strip() {
+        let mut scan = Scan::default();
+        scan.add_sensor_beacon(Vec2::new(13, 1), Vec2::new(13, 8));
+        scan.add_sensor_beacon(Vec2::new(0, -4), Vec2::new(0, 5));
+        let bounds = Rect {
+            x: InclRange::new(6, 25),
+            y: InclRange::new(-4, 1),
+        };
+        let found = scan.find_uncovered(bounds);
+        assert!(found.contains(&Vec2::new(9, -3)));
+
+        // Every uncovered cell next to a covered one, or in a corner
+        let corners = [bounds.x.min(), bounds.x.max()]
+            .map(|x| [bounds.y.min(), bounds.y.max()].map(|y| Vec2::new(x, y)));
+        let expected: Vec<_> = scan
+            .uncovered_in(bounds)
+            .into_iter()
+            .filter(|&p| {
+                corners.iter().flatten().any(|&c| c == p)
+                    || [Vec2::UP, Vec2::DOWN, Vec2::LEFT, Vec2::RIGHT]
+                        .iter()
+                        .any(|&step| scan.is_covered(p + step))
+            })
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_coverage_area() {
+        let scan = parse_scan(include_str!("test.txt"));
+        let brute_force = |rect: Rect| {
+            let mut covered = 0;
+            let mut uncovered = vec![];
+            for y in rect.y {
+                for x in rect.x {
+                    if scan.is_covered(Vec2::new(x, y)) {
+                        covered += 1;
+                    } else {
+                        uncovered.push(Vec2::new(x, y));
+                    }
+                }
+            }
+            (covered, uncovered)
+        };
+
+        let search = Rect::square(InclRange::new(0, 20));
+        let (covered, uncovered) = brute_force(search);
+        assert_eq!(scan.coverage_in(search), covered);
+        assert_eq!(scan.uncovered_in(search), uncovered);
+        assert_eq!(uncovered, [Vec2::new(14, 11)]);
+
+        let bounds = scan.bounds().unwrap();
+        assert_eq!(scan.total_coverage(), brute_force(bounds).0);
+
+        let odd = Rect {
+            x: InclRange::new(-3, 7),
+            y: InclRange::new(5, 30),
+        };
+        let (covered, uncovered) = brute_force(odd);
+        assert_eq!(scan.coverage_in(odd), covered);
+        assert_eq!(scan.uncovered_in(odd), uncovered);
+    }
 }
@@ -0,0 +1,165 @@
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, Write};
+
+use advent_2022::Vec2;
+
+use super::{Rect, Scan};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    overlaps: u32,
+    sensor: bool,
+    beacon: bool,
+    marked: bool,
+}
+
+/// A downscaled picture of a [`Scan`], where each cell stands for a block of positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heatmap {
+    rect: Rect,
+    cols: usize,
+    rows: usize,
+    cells: Vec<Cell>,
+}
+
+impl Heatmap {
+    /// Shading for increasing numbers of overlapping regions, in the ASCII rendering.
+    const SHADES: &'static [u8] = b" .:-=+*#%@";
+
+    /// Rasterize `rect` into at most `cols` by `rows` cells.
+    ///
+    /// Coverage is sampled at the center of each cell's block, while sensors and beacons
+    /// mark whichever cell they fall in.
+    pub fn render(scan: &Scan, rect: Rect, cols: usize, rows: usize) -> Self {
+        let cols = cols.clamp(1, rect.x.len() as usize);
+        let rows = rows.clamp(1, rect.y.len() as usize);
+        let mut heatmap = Self {
+            rect,
+            cols,
+            rows,
+            cells: vec![Cell::default(); cols * rows],
+        };
+
+        for row in 0..rows {
+            let y = Self::block_center(rect.y.min(), rect.y.len(), row, rows);
+            for col in 0..cols {
+                let x = Self::block_center(rect.x.min(), rect.x.len(), col, cols);
+                let point = Vec2::new(x, y);
+                heatmap.cells[row * cols + col].overlaps =
+                    scan.sensors.iter().filter(|s| s.contains(point)).count() as u32;
+            }
+        }
+        for sensor in &scan.sensors {
+            if let Some(cell) = heatmap.cell_mut(sensor.pos) {
+                cell.sensor = true;
+            }
+        }
+        for &beacon in &scan.beacons {
+            if let Some(cell) = heatmap.cell_mut(beacon) {
+                cell.beacon = true;
+            }
+        }
+        heatmap
+    }
+
+    fn block_center(min: i32, len: u64, i: usize, count: usize) -> i32 {
+        let start = len * i as u64 / count as u64;
+        let end = len * (i as u64 + 1) / count as u64;
+        min + ((start + end - 1) / 2) as i32
+    }
+
+    fn cell_mut(&mut self, point: Vec2) -> Option<&mut Cell> {
+        if !self.rect.contains(point) {
+            return None;
+        }
+        let col = (point.x - self.rect.x.min()) as u64 * self.cols as u64 / self.rect.x.len();
+        let row = (point.y - self.rect.y.min()) as u64 * self.rows as u64 / self.rect.y.len();
+        self.cells.get_mut(row as usize * self.cols + col as usize)
+    }
+
+    /// Highlight the cell containing `point`, such as a found beacon position.
+    pub fn mark(&mut self, point: Vec2) {
+        if let Some(cell) = self.cell_mut(point) {
+            cell.marked = true;
+        }
+    }
+
+    /// Write the heatmap as a binary PPM image, with one pixel per cell.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        let max_overlaps = self
+            .cells
+            .iter()
+            .map(|c| c.overlaps)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        write!(out, "P6\n{} {}\n255\n", self.cols, self.rows)?;
+        for cell in &self.cells {
+            let rgb = if cell.marked {
+                [255, 255, 0]
+            } else if cell.sensor {
+                [255, 0, 0]
+            } else if cell.beacon {
+                [0, 255, 0]
+            } else if cell.overlaps == 0 {
+                [0, 0, 0]
+            } else {
+                let shade = 64 + (191 * cell.overlaps / max_overlaps) as u8;
+                [0, shade / 2, shade]
+            };
+            out.write_all(&rgb)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Heatmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.cols) {
+            for cell in row {
+                f.write_char(if cell.marked {
+                    'X'
+                } else if cell.sensor {
+                    'S'
+                } else if cell.beacon {
+                    'B'
+                } else {
+                    let shade = (cell.overlaps as usize).min(Self::SHADES.len() - 1);
+                    Self::SHADES[shade] as char
+                })?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_scan, InclRange};
+
+    #[test]
+    fn test_render_example() {
+        let scan = parse_scan(include_str!("test.txt"));
+        let rect = Rect::square(InclRange::new(0, 20));
+        let mut heatmap = Heatmap::render(&scan, rect, 100, 100);
+        heatmap.mark(Vec2::new(14, 11));
+        let ascii = heatmap.to_string();
+        let lines: Vec<_> = ascii.lines().collect();
+        assert_eq!(lines.len(), 21);
+        assert!(lines.iter().all(|l| l.chars().count() == 21));
+        assert_eq!(lines[11].chars().nth(14), Some('X'));
+        assert_eq!(lines[18].chars().nth(2), Some('S'));
+        assert_eq!(lines[16].chars().nth(10), Some('B'));
+        assert_eq!(ascii.matches(' ').count(), 0);
+
+        let small = Heatmap::render(&scan, rect, 7, 3);
+        assert_eq!(small.to_string().lines().count(), 3);
+
+        let mut ppm = vec![];
+        small.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n7 3\n255\n"));
+        assert_eq!(ppm.len(), b"P6\n7 3\n255\n".len() + 7 * 3 * 3);
+    }
+}
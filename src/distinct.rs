@@ -0,0 +1,180 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A table of how many times each item appears in a sliding window.
+pub trait WindowCounts<T> {
+    /// Count one more `item`, returning the new count.
+    fn increment(&mut self, item: &T) -> usize;
+
+    /// Count one fewer `item`, returning the new count.
+    fn decrement(&mut self, item: &T) -> usize;
+}
+
+impl<T: Hash + Eq + Clone> WindowCounts<T> for HashMap<T, usize> {
+    fn increment(&mut self, item: &T) -> usize {
+        let count = self.entry(item.clone()).or_default();
+        *count += 1;
+        *count
+    }
+
+    fn decrement(&mut self, item: &T) -> usize {
+        let count = self.get_mut(item).expect("item was not in the window");
+        *count -= 1;
+        let count = *count;
+        if count == 0 {
+            self.remove(item);
+        }
+        count
+    }
+}
+
+impl WindowCounts<u8> for [usize; 256] {
+    fn increment(&mut self, &item: &u8) -> usize {
+        self[item as usize] += 1;
+        self[item as usize]
+    }
+
+    fn decrement(&mut self, &item: &u8) -> usize {
+        self[item as usize] -= 1;
+        self[item as usize]
+    }
+}
+
+/// Every position where the previous `n` items are all distinct.
+///
+/// Positions are the number of items read so far, so the first possible one is `n`.
+pub fn distinct_windows<I>(
+    items: I,
+    n: usize,
+) -> DistinctWindows<I::IntoIter, HashMap<I::Item, usize>>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq + Clone,
+{
+    DistinctWindows::new(items.into_iter(), n, HashMap::new())
+}
+
+/// Like [`distinct_windows`], but with a flat table in place of a hash map.
+pub fn distinct_byte_windows<I>(bytes: I, n: usize) -> DistinctWindows<I::IntoIter, [usize; 256]>
+where
+    I: IntoIterator<Item = u8>,
+{
+    DistinctWindows::new(bytes.into_iter(), n, [0; 256])
+}
+
+/// The first position where the previous `n` items are all distinct.
+pub fn first_distinct_window<I>(items: I, n: usize) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq + Clone,
+{
+    distinct_windows(items, n).next()
+}
+
+/// Tracks whether the last `n` items pushed are all distinct.
+///
+/// Runs in O(1) per item, by counting how many items in the window have a duplicate.
+#[derive(Debug, Clone)]
+pub struct DistinctTracker<T, C> {
+    n: usize,
+    window: VecDeque<T>,
+    counts: C,
+    duplicates: usize,
+}
+
+impl<T, C: WindowCounts<T>> DistinctTracker<T, C> {
+    pub fn new(n: usize, counts: C) -> Self {
+        assert_ne!(n, 0);
+        Self {
+            n,
+            window: VecDeque::with_capacity(n),
+            counts,
+            duplicates: 0,
+        }
+    }
+
+    /// Add an item to the window, returning whether the window is now full and distinct.
+    pub fn push(&mut self, item: T) -> bool {
+        if self.window.len() == self.n {
+            let old = self.window.pop_front().unwrap();
+            if self.counts.decrement(&old) == 1 {
+                self.duplicates -= 1;
+            }
+        }
+        if self.counts.increment(&item) == 2 {
+            self.duplicates += 1;
+        }
+        self.window.push_back(item);
+        self.is_distinct()
+    }
+
+    pub fn is_distinct(&self) -> bool {
+        self.window.len() == self.n && self.duplicates == 0
+    }
+}
+
+impl DistinctTracker<u8, [usize; 256]> {
+    pub fn bytes(n: usize) -> Self {
+        Self::new(n, [0; 256])
+    }
+}
+
+/// Iterator returned by [`distinct_windows`] and [`distinct_byte_windows`].
+#[derive(Debug, Clone)]
+pub struct DistinctWindows<I: Iterator, C> {
+    iter: I,
+    tracker: DistinctTracker<I::Item, C>,
+    pos: usize,
+}
+
+impl<I: Iterator, C: WindowCounts<I::Item>> DistinctWindows<I, C> {
+    pub fn new(iter: I, n: usize, counts: C) -> Self {
+        Self {
+            iter,
+            tracker: DistinctTracker::new(n, counts),
+            pos: 0,
+        }
+    }
+
+    /// The number of items read so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<I: Iterator, C: WindowCounts<I::Item>> Iterator for DistinctWindows<I, C> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for item in &mut self.iter {
+            self.pos += 1;
+            if self.tracker.push(item) {
+                return Some(self.pos);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_markers() {
+        let signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(first_distinct_window(signal.chars(), 4), Some(7));
+        assert_eq!(first_distinct_window(signal.chars(), 14), Some(19));
+        assert_eq!(distinct_byte_windows(signal.bytes(), 14).next(), Some(19));
+        assert_eq!(first_distinct_window("aaaa".chars(), 2), None);
+        assert_eq!(first_distinct_window("abc".chars(), 4), None);
+    }
+
+    #[test]
+    fn all_markers() {
+        let markers: Vec<_> = distinct_windows("aAbA1ü1".chars(), 3).collect();
+        assert_eq!(markers, [3, 5, 6]);
+        let markers: Vec<_> = distinct_byte_windows(*b"aabab", 2).collect();
+        assert_eq!(markers, [3, 4, 5]);
+    }
+}
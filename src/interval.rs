@@ -0,0 +1,246 @@
+use arrayvec::ArrayVec;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// Integer types that can bound an [`Interval`].
+pub trait IntervalBound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ONE: Self;
+
+    /// The number of values from `min` to `max` inclusive, where `min <= max`,
+    /// or `None` if that's all 2^64 values of a 64-bit type.
+    fn count(min: Self, max: Self) -> Option<u64>;
+}
+
+macro_rules! impl_interval_bound {
+    ($($t:ty),*) => {
+        $(
+            impl IntervalBound for $t {
+                const ONE: Self = 1;
+
+                fn count(min: Self, max: Self) -> Option<u64> {
+                    (max.abs_diff(min) as u64).checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_interval_bound!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A non-empty inclusive range of integers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    min: T,
+    max: T,
+}
+
+impl<T: IntervalBound> Interval<T> {
+    pub fn new(min: T, max: T) -> Self {
+        assert!(min <= max, "min was greater than max");
+        Self { min, max }
+    }
+
+    pub fn try_new(min: T, max: T) -> Option<Self> {
+        if min <= max {
+            Some(Self { min, max })
+        } else {
+            None
+        }
+    }
+
+    pub const fn from_point(point: T) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn from_center_radius(center: T, radius: T) -> Self {
+        Self::new(center - radius, center + radius)
+    }
+
+    pub const fn min(&self) -> T {
+        self.min
+    }
+
+    pub const fn max(&self) -> T {
+        self.max
+    }
+
+    /// The number of values in the interval.
+    ///
+    /// Panics if the interval covers every value of a 64-bit type, since
+    /// there are too many to count in a `u64`. Use [`checked_len`](Self::checked_len)
+    /// for intervals that might.
+    pub fn len(&self) -> u64 {
+        self.checked_len()
+            .expect("interval covers all 2^64 values of its type")
+    }
+
+    /// The number of values in the interval, or `None` if it doesn't fit in a `u64`.
+    pub fn checked_len(&self) -> Option<u64> {
+        T::count(self.min, self.max)
+    }
+
+    /// The number of values in the interval as a `u128`, which can count every
+    /// value of a 64-bit type.
+    pub fn wide_len(&self) -> u128 {
+        self.checked_len().map_or(1 << 64, u128::from)
+    }
+
+    /// Always false, since intervals cannot be empty.
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn extend_min(&self, diff: T) -> Self {
+        Self::new(self.min - diff, self.max)
+    }
+
+    pub fn extend_max(&self, diff: T) -> Self {
+        Self::new(self.min, self.max + diff)
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.min <= point && point <= self.max
+    }
+
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.min <= other.min && self.max >= other.max
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min <= other.max && self.max >= other.min
+    }
+
+    /// Whether the intervals overlap or are directly adjacent.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.overlaps(other)
+            || (self.max < other.min && self.max + T::ONE == other.min)
+            || (other.max < self.min && other.max + T::ONE == self.min)
+    }
+
+    /// Where this interval lies relative to `point`, for use with binary searches.
+    pub fn compare_point(&self, point: T) -> Ordering {
+        if point < self.min {
+            Ordering::Greater
+        } else if point > self.max {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::try_new(self.min.max(other.min), self.max.min(other.max))
+    }
+
+    /// The combined interval, if the two touch and so have no gap between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| self.hull(other))
+    }
+
+    /// The smallest interval containing both.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// The parts of this interval not in `other`, in ascending order.
+    pub fn difference(&self, other: &Self) -> ArrayVec<Self, 2> {
+        let mut parts = ArrayVec::new();
+        if !self.overlaps(other) {
+            parts.push(*self);
+            return parts;
+        }
+        if self.min < other.min {
+            parts.push(Self::new(self.min, other.min - T::ONE));
+        }
+        if self.max > other.max {
+            parts.push(Self::new(other.max + T::ONE, self.max));
+        }
+        parts
+    }
+
+    pub fn iter(&self) -> RangeInclusive<T>
+    where
+        RangeInclusive<T>: Iterator<Item = T>,
+    {
+        self.min..=self.max
+    }
+}
+
+impl<T: IntervalBound> IntoIterator for Interval<T>
+where
+    RangeInclusive<T>: Iterator<Item = T>,
+{
+    type Item = T;
+
+    type IntoIter = RangeInclusive<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(min: i32, max: i32) -> Interval<i32> {
+        Interval::new(min, max)
+    }
+
+    #[test]
+    fn interval_basic() {
+        assert_eq!(iv(-2, 3).len(), 6);
+        assert_eq!(Interval::new(0u8, 255).len(), 256);
+        assert_eq!(Interval::new(0u64, u64::MAX).checked_len(), None);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).checked_len(), None);
+        assert_eq!(
+            Interval::new(i64::MIN, i64::MAX - 1).checked_len(),
+            Some(u64::MAX)
+        );
+        assert_eq!(Interval::new(0u64, u64::MAX - 1).len(), u64::MAX);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).wide_len(), 1 << 64);
+        assert_eq!(iv(-2, 3).wide_len(), 6);
+        assert!(iv(1, 5).contains(5));
+        assert!(!iv(1, 5).contains(6));
+        assert!(iv(1, 5).contains_interval(&iv(2, 5)));
+        assert!(!iv(1, 5).contains_interval(&iv(0, 2)));
+        assert_eq!(Interval::try_new(3, 2), None);
+        assert_eq!(iv(2, 4).iter().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(iv(-1, 1).to_string(), "-1..=1");
+    }
+
+    #[test]
+    fn interval_algebra() {
+        assert!(iv(1, 3).overlaps(&iv(3, 4)));
+        assert!(!iv(1, 3).overlaps(&iv(4, 5)));
+        assert!(iv(1, 3).touches(&iv(4, 5)));
+        assert!(!iv(1, 3).touches(&iv(5, 5)));
+
+        assert_eq!(iv(1, 5).intersection(&iv(3, 8)), Some(iv(3, 5)));
+        assert_eq!(iv(1, 2).intersection(&iv(3, 8)), None);
+        assert_eq!(iv(1, 2).union(&iv(3, 8)), Some(iv(1, 8)));
+        assert_eq!(iv(1, 2).union(&iv(4, 8)), None);
+        assert_eq!(iv(1, 2).hull(&iv(4, 8)), iv(1, 8));
+
+        assert_eq!(
+            iv(1, 9).difference(&iv(3, 5)).as_slice(),
+            [iv(1, 2), iv(6, 9)]
+        );
+        assert_eq!(iv(1, 9).difference(&iv(0, 5)).as_slice(), [iv(6, 9)]);
+        assert_eq!(iv(1, 9).difference(&iv(0, 10)).as_slice(), []);
+        assert_eq!(iv(1, 2).difference(&iv(5, 10)).as_slice(), [iv(1, 2)]);
+    }
+}
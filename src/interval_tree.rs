@@ -0,0 +1,236 @@
+use crate::{Interval, IntervalBound};
+
+/// An interval along with its associated value.
+pub type IntervalEntry<T, V> = (Interval<T>, V);
+
+/// A static centered interval tree, for finding which of many intervals overlap a query.
+///
+/// Each node has a center point and holds the entries containing it, once sorted by lower
+/// bound and once by upper bound. Entries entirely below or above the center go to the
+/// node's left or right subtree. Centers are medians of the endpoints, so the tree has
+/// O(log n) depth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalTree<T, V> {
+    entries: Vec<IntervalEntry<T, V>>,
+    nodes: Vec<Node<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node<T> {
+    center: T,
+    /// Indices of the entries containing `center`, by ascending lower bound.
+    by_min: Vec<usize>,
+    /// The same entries, by descending upper bound.
+    by_max: Vec<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T: IntervalBound, V> IntervalTree<T, V> {
+    pub fn new(entries: impl IntoIterator<Item = IntervalEntry<T, V>>) -> Self {
+        let mut entries: Vec<_> = entries.into_iter().collect();
+        entries.sort_by_key(|(range, _)| (range.min(), range.max()));
+        let mut tree = Self {
+            entries,
+            nodes: vec![],
+        };
+        tree.build((0..tree.entries.len()).collect());
+        tree
+    }
+
+    /// Adds a subtree holding the entries at `indices`, which are sorted by lower bound,
+    /// and returns its root. The root is always the last node added.
+    fn build(&mut self, indices: Vec<usize>) -> Option<usize> {
+        if indices.is_empty() {
+            return None;
+        }
+        let mut endpoints: Vec<T> = indices
+            .iter()
+            .flat_map(|&i| [self.entries[i].0.min(), self.entries[i].0.max()])
+            .collect();
+        let mid = endpoints.len() / 2;
+        let center = *endpoints.select_nth_unstable(mid).1;
+
+        let range = |i: usize| self.entries[i].0;
+        let (below, rest): (Vec<_>, Vec<_>) =
+            indices.into_iter().partition(|&i| range(i).max() < center);
+        let (above, by_min): (Vec<_>, Vec<_>) =
+            rest.into_iter().partition(|&i| range(i).min() > center);
+        let mut by_max = by_min.clone();
+        by_max.sort_by_key(|&i| std::cmp::Reverse(range(i).max()));
+
+        let left = self.build(below);
+        let right = self.build(above);
+        self.nodes.push(Node {
+            center,
+            by_min,
+            by_max,
+            left,
+            right,
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    fn root(&self) -> Option<usize> {
+        self.nodes.len().checked_sub(1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries, sorted by lower bound.
+    pub fn entries(&self) -> &[IntervalEntry<T, V>] {
+        &self.entries
+    }
+
+    /// Every entry containing `point`, in no particular order, in O(log n + k).
+    pub fn stabbing(&self, point: T) -> Vec<&IntervalEntry<T, V>> {
+        let mut results = vec![];
+        self.collect_stabbing(point, &mut results);
+        results
+    }
+
+    fn collect_stabbing<'a>(&'a self, point: T, results: &mut Vec<&'a IntervalEntry<T, V>>) {
+        let mut next = self.root();
+        while let Some(node) = next {
+            let node = &self.nodes[node];
+            let entries = |indices: &'a [usize]| indices.iter().map(|&i| &self.entries[i]);
+            // Every entry here contains the center, so only one of its ends can miss `point`
+            if point < node.center {
+                results.extend(entries(&node.by_min).take_while(|e| e.0.min() <= point));
+                next = node.left;
+            } else if point > node.center {
+                results.extend(entries(&node.by_max).take_while(|e| e.0.max() >= point));
+                next = node.right;
+            } else {
+                results.extend(entries(&node.by_min));
+                break;
+            }
+        }
+    }
+
+    /// Every entry overlapping `query`, in O(log n + k). Entries containing `query.min()`
+    /// come first, in no particular order, followed by the rest sorted by lower bound.
+    pub fn overlapping(&self, query: Interval<T>) -> Vec<&IntervalEntry<T, V>> {
+        // An entry overlaps `query` if it contains its start, or starts within it
+        let mut results = vec![];
+        self.collect_stabbing(query.min(), &mut results);
+        let start = self.entries.partition_point(|e| e.0.min() <= query.min());
+        let end = self.entries.partition_point(|e| e.0.min() <= query.max());
+        results.extend(&self.entries[start..end]);
+        results
+    }
+
+    /// Every pair of overlapping entries, in O(n + k).
+    pub fn overlapping_pairs(&self) -> Vec<[&IntervalEntry<T, V>; 2]> {
+        let mut pairs = vec![];
+        for (i, a) in self.entries.iter().enumerate() {
+            // Later entries start no earlier than `a`, so they overlap iff they start within it.
+            for b in self.entries[i + 1..]
+                .iter()
+                .take_while(|b| b.0.min() <= a.0.max())
+            {
+                pairs.push([a, b]);
+            }
+        }
+        pairs
+    }
+
+    /// The number of pairs of overlapping entries, in O(n log n) without listing them.
+    pub fn overlapping_pair_count(&self) -> usize {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, a)| self.entries[i + 1..].partition_point(|b| b.0.min() <= a.0.max()))
+            .sum()
+    }
+}
+
+impl<T: IntervalBound, V> FromIterator<IntervalEntry<T, V>> for IntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = IntervalEntry<T, V>>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges() -> Vec<Interval<u32>> {
+        // Deterministic pseudo-random ranges
+        let mut state = 12345u32;
+        let mut next = || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16) % 100
+        };
+        (0..200)
+            .map(|_| {
+                let min = next();
+                Interval::new(min, min + next() % 10)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let ranges = ranges();
+        let tree: IntervalTree<u32, usize> = ranges.iter().copied().zip(0..).collect();
+        assert_eq!(tree.len(), ranges.len());
+
+        for point in 0..115 {
+            let mut found: Vec<_> = tree.stabbing(point).iter().map(|e| e.1).collect();
+            found.sort();
+            let expected: Vec<_> = (0..ranges.len())
+                .filter(|&i| ranges[i].contains(point))
+                .collect();
+            assert_eq!(found, expected, "stabbing {point}");
+        }
+
+        for min in (0..110).step_by(7) {
+            let query = Interval::new(min, min + 5);
+            let mut found: Vec<_> = tree.overlapping(query).iter().map(|e| e.1).collect();
+            found.sort();
+            let expected: Vec<_> = (0..ranges.len())
+                .filter(|&i| ranges[i].overlaps(&query))
+                .collect();
+            assert_eq!(found, expected, "overlapping {query}");
+        }
+    }
+
+    #[test]
+    fn pairs_match_brute_force() {
+        let ranges = ranges();
+        let tree: IntervalTree<u32, usize> = ranges.iter().copied().zip(0..).collect();
+        let mut found: Vec<_> = tree
+            .overlapping_pairs()
+            .into_iter()
+            .map(|[a, b]| (a.1.min(b.1), a.1.max(b.1)))
+            .collect();
+        found.sort();
+        let mut expected = vec![];
+        for i in 0..ranges.len() {
+            for j in i + 1..ranges.len() {
+                if ranges[i].overlaps(&ranges[j]) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(found, expected);
+        assert_eq!(tree.overlapping_pair_count(), expected.len());
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = IntervalTree::<u32, ()>::new([]);
+        assert!(tree.is_empty());
+        assert!(tree.stabbing(3).is_empty());
+        assert!(tree.overlapping(Interval::new(0, 5)).is_empty());
+        assert!(tree.overlapping_pairs().is_empty());
+        assert_eq!(tree.overlapping_pair_count(), 0);
+    }
+}
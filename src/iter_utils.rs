@@ -1,5 +1,10 @@
+use crate::{Histogram, StatValue, Stats};
 use arrayvec::ArrayVec;
+use std::array;
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use thiserror::Error;
 
 pub trait IteratorUtils: Iterator + Sized {
     fn max_n<const N: usize>(self) -> ArrayVec<Self::Item, N>
@@ -14,7 +19,78 @@ pub trait IteratorUtils: Iterator + Sized {
     where
         F: FnMut(&Self::Item) -> B;
 
+    /// Like [`max_n_by_key`](Self::max_n_by_key), but only calls `f` once per item.
+    fn max_n_by_cached_key<B: Ord, F, const N: usize>(self, f: F) -> ArrayVec<Self::Item, N>
+    where
+        F: FnMut(&Self::Item) -> B;
+
+    fn min_n<const N: usize>(self) -> ArrayVec<Self::Item, N>
+    where
+        Self::Item: Ord;
+
+    fn min_n_by<F, const N: usize>(self, f: F) -> ArrayVec<Self::Item, N>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering;
+
+    fn min_n_by_key<B: Ord, F, const N: usize>(self, f: F) -> ArrayVec<Self::Item, N>
+    where
+        F: FnMut(&Self::Item) -> B;
+
+    /// Heap-backed equivalent of [`max_n`](Self::max_n) for large or runtime-sized `k`.
+    ///
+    /// Runs in O(len·log k), and returns items in the same order `max_n` would.
+    fn max_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord;
+
+    fn max_k_by<F>(self, k: usize, f: F) -> Vec<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering;
+
+    fn max_k_by_key<B: Ord, F>(self, k: usize, f: F) -> Vec<Self::Item>
+    where
+        F: FnMut(&Self::Item) -> B;
+
+    /// Count, sum, min, max, mean, and median in a single pass, or `None` if empty.
+    fn stats(self) -> Option<Stats<Self::Item>>
+    where
+        Self::Item: StatValue;
+
+    /// How many times each distinct item appears.
+    fn counts(self) -> HashMap<Self::Item, usize>
+    where
+        Self::Item: Hash + Eq;
+
+    fn histogram(self, buckets: usize) -> Histogram
+    where
+        Self::Item: Into<f64>;
+
+    /// Chunks of `N` items, each followed by `S` ignored separator items.
+    ///
+    /// With `S == 0` this is a plain chunking adapter. A final short chunk is not yielded,
+    /// but is available from [`ArrayChunksSep::remainder`] once iteration ends.
     fn array_chunks_sep<const N: usize, const S: usize>(self) -> ArrayChunksSep<Self, N, S>;
+
+    /// Like [`array_chunks_sep`](Self::array_chunks_sep), but every separator item must equal
+    /// `sep`, and a final short chunk is an error rather than a remainder.
+    fn try_array_chunks_sep<const N: usize, const S: usize>(
+        self,
+        sep: Self::Item,
+    ) -> TryArrayChunksSep<Self, N, S>
+    where
+        Self::Item: PartialEq;
+
+    /// Overlapping windows of `N` consecutive items.
+    fn array_windows<const N: usize>(self) -> ArrayWindows<Self, N>
+    where
+        Self::Item: Clone;
+
+    /// Groups of items separated by items matching `pred`, which are dropped.
+    ///
+    /// Like [`slice::split`], consecutive or trailing separators produce empty groups.
+    fn split_on<F>(self, pred: F) -> SplitOn<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool;
 }
 
 #[inline]
@@ -29,6 +105,82 @@ where
         .find_map(|(i, old)| f(new, old).is_gt().then_some(i))
 }
 
+/// A min-heap of the best `k` items seen so far, ordered by `f` and then by arrival.
+///
+/// Earlier items rank above later equal items, which matches the tie order of `max_n_by`.
+struct TopK<T, F> {
+    heap: Vec<(usize, T)>,
+    k: usize,
+    f: F,
+}
+
+impl<T, F> TopK<T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    /// `size_hint` is a lower bound on how many items will be pushed, so the
+    /// heap doesn't reserve room for a huge `k` up front.
+    fn new(k: usize, size_hint: usize, f: F) -> Self {
+        Self {
+            heap: Vec::with_capacity(k.min(size_hint)),
+            k,
+            f,
+        }
+    }
+
+    fn cmp(f: &mut F, a: &(usize, T), b: &(usize, T)) -> Ordering {
+        f(&a.1, &b.1).then_with(|| b.0.cmp(&a.0))
+    }
+
+    fn push(&mut self, entry: (usize, T)) {
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+            self.sift_up(self.heap.len() - 1);
+        } else if self.k > 0 && Self::cmp(&mut self.f, &entry, &self.heap[0]).is_gt() {
+            self.heap[0] = entry;
+            self.sift_down(0);
+        }
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if Self::cmp(&mut self.f, &self.heap[i], &self.heap[parent]).is_lt() {
+                self.heap.swap(i, parent);
+                i = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len()
+                    && Self::cmp(&mut self.f, &self.heap[child], &self.heap[smallest]).is_lt()
+                {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.heap.swap(i, smallest);
+            i = smallest;
+        }
+    }
+
+    fn into_sorted_vec(self) -> Vec<T> {
+        let Self {
+            mut heap, mut f, ..
+        } = self;
+        heap.sort_unstable_by(|a, b| Self::cmp(&mut f, b, a));
+        heap.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
 impl<T: Iterator> IteratorUtils for T {
     fn max_n<const N: usize>(self) -> ArrayVec<Self::Item, N>
     where
@@ -44,6 +196,63 @@ impl<T: Iterator> IteratorUtils for T {
         self.max_n_by(|a, b| f(a).cmp(&f(b)))
     }
 
+    fn max_n_by_cached_key<B: Ord, F, const N: usize>(self, mut f: F) -> ArrayVec<Self::Item, N>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        self.map(|item| (f(&item), item))
+            .max_n_by::<_, N>(|(a, _), (b, _)| a.cmp(b))
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect()
+    }
+
+    fn min_n<const N: usize>(self) -> ArrayVec<Self::Item, N>
+    where
+        Self::Item: Ord,
+    {
+        self.max_n_by(|a, b| b.cmp(a))
+    }
+
+    fn min_n_by<F, const N: usize>(self, mut f: F) -> ArrayVec<Self::Item, N>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.max_n_by(|a, b| f(a, b).reverse())
+    }
+
+    fn min_n_by_key<B: Ord, F, const N: usize>(self, mut f: F) -> ArrayVec<Self::Item, N>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        self.max_n_by(|a, b| f(b).cmp(&f(a)))
+    }
+
+    fn max_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.max_k_by(k, |a, b| a.cmp(b))
+    }
+
+    fn max_k_by<F>(self, k: usize, f: F) -> Vec<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        let mut top = TopK::new(k, self.size_hint().0, f);
+        for entry in self.enumerate() {
+            top.push(entry);
+        }
+        top.into_sorted_vec()
+    }
+
+    fn max_k_by_key<B: Ord, F>(self, k: usize, mut f: F) -> Vec<Self::Item>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        self.max_k_by(k, |a, b| f(a).cmp(&f(b)))
+    }
+
     fn max_n_by<F, const N: usize>(mut self, mut f: F) -> ArrayVec<Self::Item, N>
     where
         F: FnMut(&Self::Item, &Self::Item) -> Ordering,
@@ -80,21 +289,109 @@ impl<T: Iterator> IteratorUtils for T {
         results
     }
 
+    fn stats(self) -> Option<Stats<Self::Item>>
+    where
+        Self::Item: StatValue,
+    {
+        Stats::from_values(self)
+    }
+
+    fn counts(self) -> HashMap<Self::Item, usize>
+    where
+        Self::Item: Hash + Eq,
+    {
+        let mut counts = HashMap::new();
+        for item in self {
+            *counts.entry(item).or_default() += 1;
+        }
+        counts
+    }
+
+    fn histogram(self, buckets: usize) -> Histogram
+    where
+        Self::Item: Into<f64>,
+    {
+        Histogram::from_values(self, buckets)
+    }
+
     fn array_chunks_sep<const N: usize, const S: usize>(self) -> ArrayChunksSep<Self, N, S> {
         assert_ne!(N, 0);
-        ArrayChunksSep(self)
+        ArrayChunksSep {
+            iter: self,
+            remainder: None,
+        }
+    }
+
+    fn try_array_chunks_sep<const N: usize, const S: usize>(
+        self,
+        sep: Self::Item,
+    ) -> TryArrayChunksSep<Self, N, S>
+    where
+        Self::Item: PartialEq,
+    {
+        assert_ne!(N, 0);
+        TryArrayChunksSep {
+            iter: self,
+            sep,
+            index: 0,
+            done: false,
+        }
+    }
+
+    fn array_windows<const N: usize>(self) -> ArrayWindows<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        assert_ne!(N, 0);
+        ArrayWindows {
+            iter: self,
+            window: None,
+        }
+    }
+
+    fn split_on<F>(self, pred: F) -> SplitOn<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+    {
+        SplitOn {
+            iter: self,
+            pred,
+            done: false,
+        }
     }
 }
 
-pub struct ArrayChunksSep<I: Iterator, const N: usize, const S: usize>(I);
+pub struct ArrayChunksSep<I: Iterator, const N: usize, const S: usize> {
+    iter: I,
+    remainder: Option<array::IntoIter<I::Item, N>>,
+}
+
+impl<I: Iterator, const N: usize, const S: usize> ArrayChunksSep<I, N, S> {
+    /// The items of a final short chunk, if iteration has reached one.
+    pub fn remainder(&self) -> Option<&[I::Item]> {
+        self.remainder.as_ref().map(|r| r.as_slice())
+    }
+
+    pub fn into_remainder(self) -> Option<array::IntoIter<I::Item, N>> {
+        self.remainder
+    }
+}
 
 impl<I: Iterator, const N: usize, const S: usize> Iterator for ArrayChunksSep<I, N, S> {
     type Item = [I::Item; N];
 
     fn next(&mut self) -> Option<Self::Item> {
-        let chunk = self.0.next_chunk().ok()?;
+        let chunk = match self.iter.next_chunk() {
+            Ok(chunk) => chunk,
+            Err(partial) => {
+                if partial.len() > 0 {
+                    self.remainder = Some(partial);
+                }
+                return None;
+            }
+        };
         for _ in 0..S {
-            if self.0.next().is_none() {
+            if self.iter.next().is_none() {
                 break;
             }
         }
@@ -102,6 +399,123 @@ impl<I: Iterator, const N: usize, const S: usize> Iterator for ArrayChunksSep<I,
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ChunkSepError<T> {
+    #[error("expected a separator at index {index}")]
+    BadSeparator { index: usize, found: T },
+    #[error("final chunk at index {index} was cut short after {} items", .partial.len())]
+    Incomplete { index: usize, partial: Vec<T> },
+}
+
+pub struct TryArrayChunksSep<I: Iterator, const N: usize, const S: usize> {
+    iter: I,
+    sep: I::Item,
+    index: usize,
+    done: bool,
+}
+
+impl<I, const N: usize, const S: usize> Iterator for TryArrayChunksSep<I, N, S>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = Result<[I::Item; N], ChunkSepError<I::Item>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.index > 0 {
+            for _ in 0..S {
+                match self.iter.next() {
+                    Some(found) if found != self.sep => {
+                        self.done = true;
+                        return Some(Err(ChunkSepError::BadSeparator {
+                            index: self.index,
+                            found,
+                        }));
+                    }
+                    Some(_) => self.index += 1,
+                    None => {
+                        self.done = true;
+                        return None;
+                    }
+                }
+            }
+        }
+        match self.iter.next_chunk() {
+            Ok(chunk) => {
+                self.index += N;
+                Some(Ok(chunk))
+            }
+            Err(partial) => {
+                self.done = true;
+                if partial.len() == 0 {
+                    return None;
+                }
+                Some(Err(ChunkSepError::Incomplete {
+                    index: self.index,
+                    partial: partial.collect(),
+                }))
+            }
+        }
+    }
+}
+
+pub struct ArrayWindows<I: Iterator, const N: usize> {
+    iter: I,
+    window: Option<[I::Item; N]>,
+}
+
+impl<I, const N: usize> Iterator for ArrayWindows<I, N>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.window {
+            Some(window) => {
+                let item = self.iter.next()?;
+                window.rotate_left(1);
+                window[N - 1] = item;
+            }
+            None => self.window = Some(self.iter.next_chunk().ok()?),
+        }
+        self.window.clone()
+    }
+}
+
+pub struct SplitOn<I, F> {
+    iter: I,
+    pred: F,
+    done: bool,
+}
+
+impl<I, F> Iterator for SplitOn<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut group = vec![];
+        for item in &mut self.iter {
+            if (self.pred)(&item) {
+                return Some(group);
+            }
+            group.push(item);
+        }
+        self.done = true;
+        Some(group)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +543,126 @@ mod tests {
         assert_eq!(just_enough.as_ref(), values);
         assert_eq!(not_enough.as_ref(), values);
     }
+
+    #[test]
+    fn min_n_basic() {
+        let result = [7, 9, 18, 0, -1, 8, 8, 9].into_iter().min_n::<3>();
+        assert_eq!(result.as_ref(), [-1, 0, 7]);
+        let result = ["ccc", "a", "bb", "dddd"]
+            .into_iter()
+            .min_n_by_key::<_, _, 2>(|s| s.len());
+        assert_eq!(result.as_ref(), ["a", "bb"]);
+    }
+
+    #[test]
+    fn max_n_cached_key() {
+        let mut calls = 0;
+        let result = [3, -7, 1, 5, -2]
+            .into_iter()
+            .max_n_by_cached_key::<_, _, 2>(|x: &i32| {
+                calls += 1;
+                x.abs()
+            });
+        assert_eq!(result.as_ref(), [-7, 5]);
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn max_k_matches_max_n() {
+        let values = [7, 9, 18, 0, -1, 8, 8, 9, 18, 3, -20, 11];
+        assert_eq!(values.into_iter().max_k(0), []);
+        assert_eq!(
+            values.into_iter().max_k(5),
+            values.into_iter().max_n::<5>().as_ref()
+        );
+        assert_eq!(
+            values.into_iter().max_k(20),
+            values.into_iter().max_n::<20>().as_ref()
+        );
+
+        // Ties keep the order they arrived in, like max_n
+        let pairs = [(1, 'a'), (3, 'b'), (1, 'c'), (3, 'd'), (2, 'e')];
+        assert_eq!(
+            pairs.into_iter().max_k_by_key(3, |p| p.0),
+            pairs.into_iter().max_n_by_key::<_, _, 3>(|p| p.0).as_ref(),
+        );
+        assert_eq!(values.into_iter().max_k(usize::MAX).len(), values.len());
+    }
+
+    #[test]
+    fn counts_basic() {
+        let counts = "abracadabra".chars().counts();
+        assert_eq!(counts.len(), 5);
+        assert_eq!(counts[&'a'], 5);
+        assert_eq!(counts[&'b'], 2);
+        assert_eq!(counts[&'d'], 1);
+    }
+
+    #[test]
+    fn array_chunks_sep_remainder() {
+        let mut chunks = "abc def gh".chars().array_chunks_sep::<3, 1>();
+        assert_eq!(chunks.next(), Some(['a', 'b', 'c']));
+        assert_eq!(chunks.next(), Some(['d', 'e', 'f']));
+        assert_eq!(chunks.remainder(), None);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), Some(&['g', 'h'][..]));
+
+        let mut chunks = (0..6).array_chunks_sep::<2, 0>();
+        assert_eq!(chunks.by_ref().count(), 3);
+        assert_eq!(chunks.remainder(), None);
+    }
+
+    #[test]
+    fn try_array_chunks_sep() {
+        let chunks: Vec<_> = "[A] [B]"
+            .chars()
+            .try_array_chunks_sep::<3, 1>(' ')
+            .collect();
+        assert_eq!(chunks, [Ok(['[', 'A', ']']), Ok(['[', 'B', ']'])]);
+
+        let chunks: Vec<_> = "[A]_[B]"
+            .chars()
+            .try_array_chunks_sep::<3, 1>(' ')
+            .collect();
+        assert_eq!(
+            chunks,
+            [
+                Ok(['[', 'A', ']']),
+                Err(ChunkSepError::BadSeparator {
+                    index: 3,
+                    found: '_'
+                }),
+            ],
+        );
+
+        let chunks: Vec<_> = "[A] [B".chars().try_array_chunks_sep::<3, 1>(' ').collect();
+        assert_eq!(
+            chunks,
+            [
+                Ok(['[', 'A', ']']),
+                Err(ChunkSepError::Incomplete {
+                    index: 4,
+                    partial: vec!['[', 'B']
+                }),
+            ],
+        );
+    }
+
+    #[test]
+    fn array_windows_basic() {
+        let windows: Vec<_> = (1..=5).array_windows::<3>().collect();
+        assert_eq!(windows, [[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+        assert_eq!((1..=2).array_windows::<3>().next(), None);
+    }
+
+    #[test]
+    fn split_on_basic() {
+        let groups: Vec<_> = [1, 2, 0, 3, 0, 0, 4]
+            .into_iter()
+            .split_on(|&x| x == 0)
+            .collect();
+        assert_eq!(groups, [vec![1, 2], vec![3], vec![], vec![4]]);
+        let groups: Vec<_> = [0, 1, 0].into_iter().split_on(|&x| x == 0).collect();
+        assert_eq!(groups, [vec![], vec![1], vec![]]);
+    }
 }
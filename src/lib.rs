@@ -1,8 +1,25 @@
 #![feature(iter_next_chunk)]
+#![feature(const_mut_refs)]
 
+mod bit_set;
+mod cycle;
+mod distinct;
+mod interval;
+mod interval_tree;
 mod iter_utils;
+mod range_set;
 mod simple_parse;
+mod stats;
+mod str_utils;
 mod vec2;
 
+pub use bit_set::*;
+pub use cycle::*;
+pub use distinct::*;
+pub use interval::*;
+pub use interval_tree::*;
 pub use iter_utils::*;
+pub use range_set::*;
+pub use stats::*;
+pub use str_utils::*;
 pub type Vec2 = vec2::Vec2<i32>;
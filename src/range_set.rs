@@ -0,0 +1,301 @@
+use crate::{Interval, IntervalBound};
+use arrayvec::ArrayVec;
+use std::cmp::Ordering;
+
+/// A set of integers, stored as sorted, disjoint, non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Interval<T>>,
+    /// A `u128`, so that a set can cover every value of a 64-bit type.
+    coverage: u128,
+}
+
+impl<T: IntervalBound> RangeSet<T> {
+    pub const fn new() -> Self {
+        Self {
+            ranges: vec![],
+            coverage: 0,
+        }
+    }
+
+    /// The number of values in the set.
+    pub fn coverage(&self) -> u128 {
+        self.coverage
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn ranges(&self) -> &[Interval<T>] {
+        self.ranges.as_slice()
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+        self.coverage = 0;
+    }
+
+    pub fn add(&mut self, ranges: Interval<T>) {
+        match self.find_intersection(ranges) {
+            Ok((min_index, max_index)) => {
+                let dropped_last = self
+                    .ranges
+                    .drain(min_index + 1..=max_index)
+                    .inspect(|r| self.coverage -= r.wide_len())
+                    .last();
+                let updated = &mut self.ranges[min_index];
+
+                self.coverage -= updated.wide_len();
+                *updated = updated.hull(&ranges);
+                if let Some(dropped_last) = dropped_last {
+                    *updated = updated.hull(&dropped_last);
+                }
+                self.coverage += updated.wide_len();
+            }
+            Err(index) => {
+                self.ranges.insert(index, ranges);
+                self.coverage += ranges.wide_len();
+            }
+        }
+    }
+
+    pub fn remove(&mut self, range: Interval<T>) {
+        let start = self.ranges.partition_point(|r| r.max() < range.min());
+        let end = self.ranges.partition_point(|r| r.min() <= range.max());
+        if start >= end {
+            return;
+        }
+
+        // Only the first and last overlapped ranges can stick out past `range`.
+        let mut kept = ArrayVec::<_, 2>::new();
+        for r in self.ranges.drain(start..end) {
+            self.coverage -= r.wide_len();
+            kept.extend(r.difference(&range));
+        }
+        self.coverage += kept.iter().map(|r| r.wide_len()).sum::<u128>();
+        self.ranges.splice(start..start, kept);
+    }
+
+    pub fn contains_point(&self, point: T) -> bool {
+        self.ranges
+            .binary_search_by(|r| r.compare_point(point))
+            .is_ok()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.ranges.iter().copied());
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            // Pieces of non-adjacent ranges can't be adjacent either, so no merging needed.
+            if let Some(r) = a.intersection(b) {
+                result.ranges.push(r);
+                result.coverage += r.wide_len();
+            }
+            if a.max() < b.max() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &r in &other.ranges {
+            result.remove(r);
+        }
+        result
+    }
+
+    /// Every value within `bounds` that is not in the set.
+    pub fn complement(&self, bounds: Interval<T>) -> Self {
+        let mut result = Self::from_iter([bounds]);
+        for &r in &self.ranges {
+            result.remove(r);
+        }
+        result
+    }
+
+    /// The uncovered ranges between each pair of consecutive ranges.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.ranges
+            .windows(2)
+            .map(|w| Interval::new(w[0].max() + T::ONE, w[1].min() - T::ONE))
+    }
+
+    /// The smallest value within `bounds` that is not in the set, without allocating.
+    pub fn first_uncovered(&self, bounds: Interval<T>) -> Option<T> {
+        let mut next = bounds.min();
+        for r in &self.ranges {
+            if r.max() < next {
+                continue;
+            }
+            if r.min() > next {
+                break;
+            }
+            if r.max() >= bounds.max() {
+                return None;
+            }
+            next = r.max() + T::ONE;
+        }
+        bounds.contains(next).then_some(next)
+    }
+
+    fn find_intersection(&self, ranges: Interval<T>) -> Result<(usize, usize), usize> {
+        // Ranges that touch `ranges` count as intersecting, so that they get merged.
+        let min_index = self.ranges.binary_search_by(|r| {
+            if r.max() < ranges.min() && r.max() + T::ONE < ranges.min() {
+                Ordering::Less
+            } else {
+                r.compare_point(ranges.min()).max(Ordering::Equal)
+            }
+        });
+        let max_index = self.ranges.binary_search_by(|r| {
+            if ranges.max() < r.min() && ranges.max() + T::ONE < r.min() {
+                Ordering::Greater
+            } else {
+                r.compare_point(ranges.max()).min(Ordering::Equal)
+            }
+        });
+        match (min_index, max_index) {
+            (Ok(a), Ok(b)) => Ok((a, b)),
+            (Ok(a), Err(b)) => Ok((a, b - 1)),
+            (Err(a), Ok(b)) => Ok((a, b)),
+            (Err(a), Err(b)) => {
+                if a == b {
+                    Err(a)
+                } else {
+                    Ok((a, b - 1))
+                }
+            }
+        }
+    }
+}
+
+impl<T: IntervalBound> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntervalBound> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: IntervalBound> Extend<Interval<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.add(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(min: i32, max: i32) -> Interval<i32> {
+        Interval::new(min, max)
+    }
+
+    #[test]
+    fn test_range_set() {
+        let mut ranges = RangeSet::default();
+        let r12 = Interval::try_new(1, 2).unwrap();
+        let r33 = Interval::try_new(3, 3).unwrap();
+        let r48 = Interval::try_new(4, 8).unwrap();
+        ranges.add(r12);
+        ranges.add(r48);
+        assert_eq!(ranges.coverage(), 7);
+        assert_eq!(ranges.ranges(), [r12, r48]);
+        ranges.add(r33);
+        assert_eq!(ranges.coverage(), 8);
+        assert_eq!(ranges.ranges(), [Interval::try_new(1, 8).unwrap()]);
+    }
+
+    #[test]
+    fn add_at_type_limits() {
+        let mut ranges: RangeSet<u8> = [Interval::new(250, 255), Interval::new(0, 3)]
+            .into_iter()
+            .collect();
+        ranges.add(Interval::new(4, 4));
+        ranges.add(Interval::new(253, 254));
+        assert_eq!(
+            ranges.ranges(),
+            [Interval::new(0, 4), Interval::new(250, 255)]
+        );
+        assert_eq!(ranges.coverage(), 11);
+    }
+
+    #[test]
+    fn full_width_64_bit() {
+        let mut ranges = RangeSet::new();
+        ranges.add(Interval::new(0u64, u64::MAX / 2));
+        ranges.add(Interval::new(u64::MAX / 2 + 2, u64::MAX));
+        assert_eq!(ranges.coverage(), (1 << 64) - 1);
+        ranges.add(Interval::new(0, u64::MAX));
+        assert_eq!(ranges.ranges(), [Interval::new(0, u64::MAX)]);
+        assert_eq!(ranges.coverage(), 1 << 64);
+        ranges.remove(Interval::new(1, u64::MAX - 1));
+        assert_eq!(ranges.coverage(), 2);
+
+        let all = Interval::new(i64::MIN, i64::MAX);
+        let mut ranges: RangeSet<i64> = [all].into_iter().collect();
+        assert_eq!(ranges.coverage(), 1 << 64);
+        ranges.remove(Interval::new(-1, 1));
+        assert_eq!(ranges.coverage(), (1 << 64) - 3);
+        assert_eq!(RangeSet::new().complement(all).coverage(), 1 << 64);
+    }
+
+    #[test]
+    fn remove() {
+        let mut ranges: RangeSet<i32> = [iv(1, 5), iv(8, 10), iv(12, 20)].into_iter().collect();
+        ranges.remove(iv(3, 13));
+        assert_eq!(ranges.ranges(), [iv(1, 2), iv(14, 20)]);
+        assert_eq!(ranges.coverage(), 9);
+        ranges.remove(iv(16, 16));
+        assert_eq!(ranges.ranges(), [iv(1, 2), iv(14, 15), iv(17, 20)]);
+        ranges.remove(iv(-5, 0));
+        assert_eq!(ranges.coverage(), 8);
+        assert!(!ranges.contains_point(16));
+        assert!(ranges.contains_point(17));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: RangeSet<i32> = [iv(0, 4), iv(10, 14)].into_iter().collect();
+        let b: RangeSet<i32> = [iv(3, 11), iv(20, 20)].into_iter().collect();
+        assert_eq!(a.union(&b).ranges(), [iv(0, 14), iv(20, 20)]);
+        assert_eq!(a.intersection(&b).ranges(), [iv(3, 4), iv(10, 11)]);
+        assert_eq!(a.intersection(&b).coverage(), 4);
+        assert_eq!(a.difference(&b).ranges(), [iv(0, 2), iv(12, 14)]);
+        assert_eq!(b.difference(&a).ranges(), [iv(5, 9), iv(20, 20)]);
+        assert_eq!(a.complement(iv(-2, 12)).ranges(), [iv(-2, -1), iv(5, 9)]);
+        assert_eq!(a.gaps().collect::<Vec<_>>(), [iv(5, 9)]);
+        assert_eq!(b.gaps().collect::<Vec<_>>(), [iv(12, 19)]);
+    }
+
+    #[test]
+    fn first_uncovered() {
+        let a: RangeSet<i32> = [iv(0, 4), iv(10, 14)].into_iter().collect();
+        assert_eq!(a.first_uncovered(iv(-3, 20)), Some(-3));
+        assert_eq!(a.first_uncovered(iv(0, 20)), Some(5));
+        assert_eq!(a.first_uncovered(iv(2, 4)), None);
+        assert_eq!(a.first_uncovered(iv(3, 12)), Some(5));
+        assert_eq!(a.first_uncovered(iv(11, 20)), Some(15));
+        assert_eq!(a.first_uncovered(iv(11, 14)), None);
+        assert_eq!(a.first_uncovered(iv(30, 40)), Some(30));
+    }
+}
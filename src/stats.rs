@@ -0,0 +1,168 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Integer types that [`Stats`] can summarize.
+pub trait StatValue: Copy + Ord {
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_stat_value {
+    ($($t:ty),*) => {
+        $(
+            impl StatValue for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*
+    };
+}
+
+impl_stat_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Summary statistics for a non-empty sequence of values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats<T> {
+    pub count: usize,
+    /// Kept as an `i128` so that summing many large values can't overflow.
+    pub sum: i128,
+    pub min: T,
+    pub max: T,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl<T: StatValue> Stats<T> {
+    /// Returns `None` if `values` is empty.
+    pub fn from_values(values: impl IntoIterator<Item = T>) -> Option<Self> {
+        let mut values = values.into_iter();
+        let first = values.next()?;
+        let mut all = vec![first];
+        let (mut sum, mut min, mut max) = (first.to_i128(), first, first);
+        for value in values {
+            sum += value.to_i128();
+            min = min.min(value);
+            max = max.max(value);
+            all.push(value);
+        }
+
+        let count = all.len();
+        let mid = count / 2;
+        let (lower, &mut upper, _) = all.select_nth_unstable(mid);
+        let median = if count % 2 == 0 {
+            let lower = *lower.iter().max().unwrap();
+            (lower.to_i128() + upper.to_i128()) as f64 / 2.0
+        } else {
+            upper.to_i128() as f64
+        };
+
+        Some(Self {
+            count,
+            sum,
+            min,
+            max,
+            mean: sum as f64 / count as f64,
+            median,
+        })
+    }
+}
+
+/// Counts of values falling into equal-width buckets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub min: f64,
+    pub bucket_width: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Split the range from the smallest to the largest value into `buckets` buckets.
+    pub fn from_values<T: Into<f64>>(values: impl IntoIterator<Item = T>, buckets: usize) -> Self {
+        assert_ne!(buckets, 0);
+        let values: Vec<f64> = values.into_iter().map(Into::into).collect();
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mut counts = vec![0; buckets];
+        if values.is_empty() {
+            return Self {
+                min: 0.0,
+                bucket_width: 0.0,
+                counts,
+            };
+        }
+
+        let bucket_width = (max - min) / buckets as f64;
+        for value in values {
+            let i = if bucket_width > 0.0 {
+                ((value - min) / bucket_width) as usize
+            } else {
+                0
+            };
+            counts[i.min(buckets - 1)] += 1;
+        }
+        Self {
+            min,
+            bucket_width,
+            counts,
+        }
+    }
+
+    pub fn bucket_start(&self, i: usize) -> f64 {
+        self.min + self.bucket_width * i as f64
+    }
+}
+
+impl Display for Histogram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const BAR_WIDTH: usize = 40;
+        let max_count = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in self.counts.iter().enumerate() {
+            let bar = count * BAR_WIDTH / max_count;
+            writeln!(
+                f,
+                "{:>12.1} | {:<BAR_WIDTH$} {count}",
+                self.bucket_start(i),
+                "#".repeat(bar),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_values([5u32, 1, 4, 2, 8]).unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.sum, 20);
+        assert_eq!((stats.min, stats.max), (1, 8));
+        assert_eq!(stats.mean, 4.0);
+        assert_eq!(stats.median, 4.0);
+
+        let stats = Stats::from_values([-3i32, 10, 1, 2]).unwrap();
+        assert_eq!(stats.median, 1.5);
+        assert_eq!(Stats::<u32>::from_values([]), None);
+
+        // Neither the sum nor the median overflows
+        let stats = Stats::from_values([u64::MAX, u64::MAX - 1, u64::MAX - 3, 7]).unwrap();
+        assert_eq!(stats.sum, 3 * u64::MAX as i128 - 4 + 7);
+        assert_eq!((stats.min, stats.max), (7, u64::MAX));
+        assert_eq!(stats.median, (2 * u64::MAX as i128 - 4) as f64 / 2.0);
+        let stats = Stats::from_values([i64::MIN, i64::MIN]).unwrap();
+        assert_eq!(stats.sum, 2 * i64::MIN as i128);
+        assert_eq!(stats.mean, i64::MIN as f64);
+    }
+
+    #[test]
+    fn histogram() {
+        let hist = Histogram::from_values([0u8, 1, 2, 5, 9, 10], 5);
+        assert_eq!(hist.bucket_width, 2.0);
+        assert_eq!(hist.counts, [2, 1, 1, 0, 2]);
+        assert_eq!(hist.bucket_start(3), 6.0);
+
+        let hist = Histogram::from_values([3u8, 3, 3], 4);
+        assert_eq!(hist.counts, [3, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,101 @@
+use std::iter::Enumerate;
+use std::str::{Lines, SplitInclusive};
+
+pub trait StrUtils {
+    /// Split into groups of lines separated by blank or whitespace-only lines.
+    ///
+    /// Both `\n` and `\r\n` line endings are accepted, and leading, trailing, or repeated
+    /// separators never produce empty groups.
+    fn paragraphs(&self) -> Paragraphs<'_>;
+}
+
+impl StrUtils for str {
+    fn paragraphs(&self) -> Paragraphs<'_> {
+        Paragraphs {
+            text: self,
+            lines: self.split_inclusive('\n').enumerate(),
+            offset: 0,
+        }
+    }
+}
+
+/// A group of consecutive non-blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paragraph<'a> {
+    /// The 1-based line number of the first line in the group.
+    pub start_line: usize,
+    /// The text of the group, without the final line ending.
+    pub text: &'a str,
+}
+
+impl<'a> Paragraph<'a> {
+    pub fn lines(&self) -> Lines<'a> {
+        self.text.lines()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Paragraphs<'a> {
+    text: &'a str,
+    lines: Enumerate<SplitInclusive<'a, char>>,
+    offset: usize,
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = Paragraph<'a>;
+
+    fn next(&mut self) -> Option<Paragraph<'a>> {
+        let mut start: Option<(usize, usize)> = None;
+        let mut end = self.offset;
+        for (i, line) in &mut self.lines {
+            let line_start = self.offset;
+            self.offset += line.len();
+            let content = line.trim_end_matches('\n').trim_end_matches('\r');
+            if content.trim().is_empty() {
+                if start.is_some() {
+                    break;
+                }
+            } else {
+                start.get_or_insert((i + 1, line_start));
+                end = line_start + content.len();
+            }
+        }
+        let (start_line, start) = start?;
+        Some(Paragraph {
+            start_line,
+            text: &self.text[start..end],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(text: &str) -> Vec<(usize, Vec<&str>)> {
+        text.paragraphs()
+            .map(|p| (p.start_line, p.lines().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn paragraphs_basic() {
+        assert_eq!(
+            collect("1\n2\n\n3\n\n4\n5\n6"),
+            [
+                (1, vec!["1", "2"]),
+                (4, vec!["3"]),
+                (6, vec!["4", "5", "6"])
+            ],
+        );
+        assert_eq!(collect(""), []);
+        assert_eq!(collect("\n\n"), []);
+    }
+
+    #[test]
+    fn paragraphs_messy_separators() {
+        let text = "\r\n  \r\na\r\nb\r\n\t\r\n\r\nc\r\n\r\n \n";
+        assert_eq!(collect(text), [(3, vec!["a", "b"]), (7, vec!["c"])]);
+        assert_eq!(text.paragraphs().next().unwrap().text, "a\r\nb");
+    }
+}